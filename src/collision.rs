@@ -0,0 +1,9 @@
+mod collision_event;
+mod collision_layers;
+mod collision_matrix;
+mod spatial_query;
+
+pub use collision_event::*;
+pub use collision_layers::*;
+pub use collision_matrix::*;
+pub use spatial_query::*;
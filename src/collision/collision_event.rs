@@ -0,0 +1,22 @@
+use crate::structs::{GameObjectType, SweepHit};
+
+///
+/// CollisionEvent definition
+///
+/// A contact between two objects whose pair is registered in a `CollisionMatrix`. `first` is
+/// the object that was moved along its path to find the contact.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CollisionEvent {
+    pub first: (GameObjectType, u32),
+    pub second: (GameObjectType, u32),
+    pub hit: SweepHit,
+}
+
+///
+/// CollisionEvent implementation
+///
+impl CollisionEvent {
+    pub fn pair(&self) -> (GameObjectType, GameObjectType) {
+        (self.first.0, self.second.0)
+    }
+}
@@ -0,0 +1,48 @@
+use crate::structs::GameObjectType;
+
+///
+/// CollisionLayers definition
+///
+/// Bit set with one layer per `GameObjectType`. An object is on the layer of its type, and its
+/// mask holds the layers it is tested against.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CollisionLayers(u32);
+
+///
+/// CollisionLayers implementation
+///
+impl CollisionLayers {
+    pub const NONE: CollisionLayers = CollisionLayers(0);
+    pub const ALL: CollisionLayers = CollisionLayers(u32::MAX);
+
+    pub fn of(game_object_type: GameObjectType) -> Self {
+        let bit = match game_object_type {
+            GameObjectType::Player => 0,
+            GameObjectType::Coin => 1,
+            GameObjectType::Boundary => 2,
+            GameObjectType::Projectile => 3,
+        };
+
+        CollisionLayers(1 << bit)
+    }
+
+    pub fn without(self, game_object_type: GameObjectType) -> Self {
+        CollisionLayers(self.0 & !CollisionLayers::of(game_object_type).0)
+    }
+
+    pub fn with(self, game_object_type: GameObjectType) -> Self {
+        CollisionLayers(self.0 | CollisionLayers::of(game_object_type).0)
+    }
+
+    pub fn union(&self, other: &CollisionLayers) -> Self {
+        CollisionLayers(self.0 | other.0)
+    }
+
+    pub fn contains(&self, game_object_type: GameObjectType) -> bool {
+        self.0 & CollisionLayers::of(game_object_type).0 != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
@@ -0,0 +1,226 @@
+use crate::collision::{CollisionEvent, CollisionLayers};
+use crate::structs::{GameObjectType, SpatialGrid};
+use crate::traits::GameObject;
+use std::collections::{HashMap, HashSet};
+
+/// Called with the context (usually the game) for every collision of a registered pair
+pub type CollisionHandler<C> = fn(&mut C, &CollisionEvent);
+
+///
+/// CollisionMatrix definition
+///
+/// Declares which pairs of object types collide and what happens when they do. `detect` finds
+/// every collision in one pass, using a broadphase grid so only objects with overlapping boxes
+/// are compared pixel by pixel.
+pub struct CollisionMatrix<C> {
+    // Layers each object type is tested against
+    masks: HashMap<GameObjectType, CollisionLayers>,
+    handlers: HashMap<(GameObjectType, GameObjectType), CollisionHandler<C>>,
+    // Objects that never move (e.g. terrain), registered by their collision points once
+    static_grid: SpatialGrid<(GameObjectType, u32)>,
+    static_objects: HashSet<(GameObjectType, u32)>,
+}
+
+///
+/// CollisionMatrix implementation
+///
+impl<C> CollisionMatrix<C> {
+    pub fn new() -> Self {
+        Self {
+            masks: HashMap::new(),
+            handlers: HashMap::new(),
+            static_grid: SpatialGrid::default(),
+            static_objects: HashSet::new(),
+        }
+    }
+
+    /// Tests objects of type `first` against objects of type `second`, calling `handler` for
+    /// each contact. `first` is swept along its path, so it should be the faster of the two.
+    pub fn register(
+        &mut self,
+        first: GameObjectType,
+        second: GameObjectType,
+        handler: CollisionHandler<C>,
+    ) {
+        let mask = self.mask(first).with(second);
+        self.masks.insert(first, mask);
+        self.handlers.insert((first, second), handler);
+    }
+
+    pub fn with_pair(
+        mut self,
+        first: GameObjectType,
+        second: GameObjectType,
+        handler: CollisionHandler<C>,
+    ) -> Self {
+        self.register(first, second, handler);
+        self
+    }
+
+    pub fn mask(&self, game_object_type: GameObjectType) -> CollisionLayers {
+        self.masks
+            .get(&game_object_type)
+            .copied()
+            .unwrap_or(CollisionLayers::NONE)
+    }
+
+    pub fn handler(
+        &self,
+        (first, second): (GameObjectType, GameObjectType),
+    ) -> Option<CollisionHandler<C>> {
+        self.handlers.get(&(first, second)).copied()
+    }
+
+    /// Registers an object that never moves. Static objects are still passed to `detect`, but
+    /// are looked up through a grid built once instead of every pass.
+    pub fn add_static(&mut self, object: &dyn GameObject) {
+        self.static_grid
+            .insert_points(object.identity(), object.solid_points());
+        self.static_objects.insert(object.identity());
+    }
+
+    pub fn clear_static(&mut self) {
+        self.static_grid.clear();
+        self.static_objects.clear();
+    }
+
+    /// Finds every collision between `objects` for the registered pairs, in the order of
+    /// `objects`. The collisions of each object are ordered by how far along its path they
+    /// happen, nearest first.
+    pub fn detect(&self, objects: &[&dyn GameObject]) -> Vec<CollisionEvent> {
+        let objects_by_identity: HashMap<(GameObjectType, u32), &dyn GameObject> = objects
+            .iter()
+            .map(|object| (object.identity(), *object))
+            .collect();
+
+        // Only objects that something is tested against need to be in the grid
+        let tested_layers = self
+            .masks
+            .values()
+            .fold(CollisionLayers::NONE, |layers, mask| layers.union(mask));
+        let mut dynamic_grid: SpatialGrid<(GameObjectType, u32)> = SpatialGrid::default();
+        for object in objects {
+            if !tested_layers.contains(object.game_object_type())
+                || self.static_objects.contains(&object.identity())
+            {
+                continue;
+            }
+            if let Some(bounds) = object.swept_collision_bounds() {
+                dynamic_grid.insert(object.identity(), bounds);
+            }
+        }
+
+        let mut collisions = Vec::new();
+        for first in objects {
+            let mask = self.mask(first.game_object_type());
+            if mask.is_empty() {
+                continue;
+            }
+            let bounds = match first.swept_collision_bounds() {
+                Some(bounds) => bounds,
+                None => continue,
+            };
+
+            let mut candidates = self.static_grid.query(&bounds);
+            candidates.extend(dynamic_grid.query(&bounds));
+            let first_collisions_start = collisions.len();
+
+            for identity in candidates {
+                if identity == first.identity() || !mask.contains(identity.0) {
+                    continue;
+                }
+                let second = match objects_by_identity.get(&identity) {
+                    Some(second) => second,
+                    // A static object that is no longer passed in
+                    None => continue,
+                };
+
+                if let Some(hit) = first.sweep_against(*second) {
+                    collisions.push(CollisionEvent {
+                        first: first.identity(),
+                        second: identity,
+                        hit,
+                    });
+                }
+            }
+
+            let path_start = first.prev_origin().unwrap_or(first.origin());
+            collisions[first_collisions_start..].sort_by_key(|collision| {
+                let (dx, dy) = (
+                    (collision.hit.origin.x - path_start.x) as i64,
+                    (collision.hit.origin.y - path_start.y) as i64,
+                );
+                dx * dx + dy * dy
+            });
+        }
+
+        collisions
+    }
+}
+
+impl<C> Default for CollisionMatrix<C> {
+    fn default() -> Self {
+        CollisionMatrix::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_objects::{Boundary, Coin, Projectile};
+    use crate::structs::{Direction, Point, Rotation};
+
+    fn ignore_collision(_context: &mut (), _collision: &CollisionEvent) {}
+
+    #[test]
+    fn collisions_of_an_object_are_ordered_along_its_path() {
+        let boundary = Boundary::new(200, 200);
+        let coin = Coin::new(Point::new(60, 60));
+        // Moves through the coin and into the top wall in a single step
+        let mut projectile = Projectile::new(Point::new(60, 150), Direction::Up, Rotation::Up);
+        projectile.set_origin(Point::new(60, -10));
+
+        let mut collisions = CollisionMatrix::new()
+            .with_pair(
+                GameObjectType::Projectile,
+                GameObjectType::Boundary,
+                ignore_collision,
+            )
+            .with_pair(
+                GameObjectType::Projectile,
+                GameObjectType::Coin,
+                ignore_collision,
+            );
+        collisions.add_static(&boundary);
+
+        let events = collisions.detect(&[&boundary, &coin, &projectile]);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].first, projectile.identity());
+        assert_eq!(events[0].second, coin.identity());
+        assert_eq!(events[1].second, boundary.identity());
+        assert!(events[0].hit.origin.y > events[1].hit.origin.y);
+        // The wall stops the projectile instead of letting it pass
+        assert!(events[1].hit.contact_point.y >= 0);
+    }
+
+    #[test]
+    fn unregistered_pairs_are_ignored() {
+        let boundary = Boundary::new(200, 200);
+        let coin = Coin::new(Point::new(60, 60));
+        let mut projectile = Projectile::new(Point::new(60, 150), Direction::Up, Rotation::Up);
+        projectile.set_origin(Point::new(60, -10));
+
+        let mut collisions: CollisionMatrix<()> = CollisionMatrix::new().with_pair(
+            GameObjectType::Projectile,
+            GameObjectType::Boundary,
+            ignore_collision,
+        );
+        collisions.add_static(&boundary);
+
+        let events = collisions.detect(&[&boundary, &coin, &projectile]);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].second, boundary.identity());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::collision::CollisionLayers;
+use crate::structs::{BoundingBox, GameObjectType, Point};
+use crate::traits::GameObject;
+use crate::util::swept_origins;
+use std::collections::HashSet;
+
+///
+/// RaycastHit definition
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RaycastHit {
+    pub object: (GameObjectType, u32),
+    // First point along the ray inside the object's collision points
+    pub point: Point,
+    // From the ray origin to `point`, in pixels
+    pub distance: f32,
+}
+
+/// First object on `layers` hit by a ray from `origin` in `direction`, within `max_distance`
+/// pixels. `direction` does not need to be normalized.
+pub fn raycast(
+    objects: &[&dyn GameObject],
+    origin: Point,
+    direction: (f32, f32),
+    max_distance: u32,
+    layers: CollisionLayers,
+) -> Option<RaycastHit> {
+    let length = (direction.0 * direction.0 + direction.1 * direction.1).sqrt();
+    if length == 0.0 {
+        return None;
+    }
+
+    let end = Point::new(
+        origin.x + (direction.0 / length * max_distance as f32).round() as i32,
+        origin.y + (direction.1 / length * max_distance as f32).round() as i32,
+    );
+    let ray_bounds = BoundingBox::new(origin, origin).union(&BoundingBox::new(end, end));
+
+    // Only objects whose box the ray can pass through need to be checked at each step
+    let candidates: Vec<&dyn GameObject> = objects
+        .iter()
+        .filter(|object| layers.contains(object.game_object_type()))
+        .filter(|object| {
+            object
+                .collision_bounds()
+                .is_some_and(|bounds| bounds.intersects(&ray_bounds))
+        })
+        .copied()
+        .collect();
+
+    std::iter::once(origin)
+        .chain(swept_origins(origin, end).filter(move |point| *point != origin))
+        .find_map(|point| {
+            candidates
+                .iter()
+                .find(|object| object.is_solid_at(point))
+                .map(|object| RaycastHit {
+                    object: object.identity(),
+                    point,
+                    distance: ((point.x - origin.x) as f32).hypot((point.y - origin.y) as f32),
+                })
+        })
+}
+
+/// Objects on `layers` with a collision point at `point`
+pub fn objects_at(
+    objects: &[&dyn GameObject],
+    point: Point,
+    layers: CollisionLayers,
+) -> Vec<(GameObjectType, u32)> {
+    objects
+        .iter()
+        .filter(|object| layers.contains(object.game_object_type()))
+        .filter(|object| object.is_solid_at(point))
+        .map(|object| object.identity())
+        .collect()
+}
+
+/// Objects on `layers` with at least one collision point inside `bounds`
+pub fn objects_in_rect(
+    objects: &[&dyn GameObject],
+    bounds: &BoundingBox,
+    layers: CollisionLayers,
+) -> Vec<(GameObjectType, u32)> {
+    objects
+        .iter()
+        .filter(|object| layers.contains(object.game_object_type()))
+        .filter(|object| {
+            // Only the part of `bounds` the object's box overlaps can hold its points
+            let overlap = match object.collision_bounds() {
+                Some(object_bounds) if object_bounds.intersects(bounds) => BoundingBox::new(
+                    Point::new(
+                        bounds.min.x.max(object_bounds.min.x),
+                        bounds.min.y.max(object_bounds.min.y),
+                    ),
+                    Point::new(
+                        bounds.max.x.min(object_bounds.max.x),
+                        bounds.max.y.min(object_bounds.max.y),
+                    ),
+                ),
+                _ => return false,
+            };
+
+            (overlap.min.y..=overlap.max.y)
+                .flat_map(|y| (overlap.min.x..=overlap.max.x).map(move |x| Point::new(x, y)))
+                .any(|point| object.is_solid_at(point))
+        })
+        .map(|object| object.identity())
+        .collect()
+}
+
+/// Objects on `layers` sharing at least one collision point with `points` (in world space)
+pub fn objects_overlapping(
+    objects: &[&dyn GameObject],
+    points: &HashSet<Point>,
+    layers: CollisionLayers,
+) -> Vec<(GameObjectType, u32)> {
+    let bounds = match BoundingBox::from_points(points) {
+        Some(bounds) => bounds,
+        None => return Vec::new(),
+    };
+
+    objects
+        .iter()
+        .filter(|object| layers.contains(object.game_object_type()))
+        .filter(|object| {
+            object
+                .collision_bounds()
+                .is_some_and(|object_bounds| object_bounds.intersects(&bounds))
+        })
+        .filter(|object| points.iter().any(|point| object.is_solid_at(*point)))
+        .map(|object| object.identity())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_objects::{Boundary, Player};
+
+    #[test]
+    fn raycast_hits_nearest_object_on_layers() {
+        let boundary = Boundary::new(200, 200);
+        let player = Player::new(Point::new(100, 100));
+        let player_bounds = player.collision_bounds().unwrap();
+        let objects: [&dyn GameObject; 2] = [&boundary, &player];
+        let origin = Point::new(20, player_bounds.min.y);
+
+        // The player stands between the origin and the right wall
+        let hit = raycast(&objects, origin, (1.0, 0.0), 300, CollisionLayers::ALL).unwrap();
+        assert_eq!(hit.object, player.identity());
+        assert!(hit.point.x >= player_bounds.min.x && hit.point.x <= player_bounds.max.x);
+
+        let hit = raycast(
+            &objects,
+            origin,
+            (1.0, 0.0),
+            300,
+            CollisionLayers::of(GameObjectType::Boundary),
+        )
+        .unwrap();
+        assert_eq!(hit.object, boundary.identity());
+        assert_eq!(hit.point, Point::new(195, origin.y));
+        assert_eq!(hit.distance, 175.0);
+
+        assert_eq!(
+            raycast(
+                &objects,
+                origin,
+                (1.0, 0.0),
+                100,
+                CollisionLayers::of(GameObjectType::Boundary)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn raycast_distance_does_not_overflow_far_from_origin() {
+        let boundary = Boundary::new(200, 200);
+        let objects: [&dyn GameObject; 1] = [&boundary];
+
+        let hit = raycast(
+            &objects,
+            Point::new(-60_000, 100),
+            (1.0, 0.0),
+            70_000,
+            CollisionLayers::ALL,
+        )
+        .unwrap();
+
+        assert_eq!(hit.point, Point::new(0, 100));
+        assert_eq!(hit.distance, 60_000.0);
+    }
+
+    #[test]
+    fn objects_at_filters_by_layers() {
+        let boundary = Boundary::new(200, 200);
+        let player = Player::new(Point::new(100, 100));
+        let player_point = player.solid_points().next().unwrap();
+        let objects: [&dyn GameObject; 2] = [&boundary, &player];
+
+        assert_eq!(
+            objects_at(&objects, Point::new(2, 2), CollisionLayers::ALL),
+            vec![boundary.identity()]
+        );
+        assert_eq!(
+            objects_at(&objects, player_point, CollisionLayers::ALL),
+            vec![player.identity()]
+        );
+        assert!(objects_at(
+            &objects,
+            player_point,
+            CollisionLayers::ALL.without(GameObjectType::Player)
+        )
+        .is_empty());
+        assert!(objects_at(&objects, Point::new(50, 50), CollisionLayers::ALL).is_empty());
+    }
+
+    #[test]
+    fn objects_in_rect_finds_partially_covered_objects() {
+        let boundary = Boundary::new(200, 200);
+        let player = Player::new(Point::new(100, 100));
+        let player_bounds = player.collision_bounds().unwrap();
+        let objects: [&dyn GameObject; 2] = [&boundary, &player];
+
+        // Reaches from the open floor into the player's box, but not into a wall
+        let rect = BoundingBox::new(Point::new(20, 20), player_bounds.max);
+        assert_eq!(
+            objects_in_rect(&objects, &rect, CollisionLayers::ALL),
+            vec![player.identity()]
+        );
+
+        let rect = BoundingBox::new(Point::new(0, 20), player_bounds.max);
+        assert_eq!(
+            objects_in_rect(&objects, &rect, CollisionLayers::ALL),
+            vec![boundary.identity(), player.identity()]
+        );
+
+        let rect = BoundingBox::new(Point::new(20, 20), Point::new(30, 30));
+        assert!(objects_in_rect(&objects, &rect, CollisionLayers::ALL).is_empty());
+    }
+
+    #[test]
+    fn objects_overlapping_needs_a_shared_point() {
+        let boundary = Boundary::new(200, 200);
+        let player = Player::new(Point::new(100, 100));
+        let objects: [&dyn GameObject; 2] = [&boundary, &player];
+
+        let points: HashSet<Point> = player.solid_points().collect();
+        assert_eq!(
+            objects_overlapping(&objects, &points, CollisionLayers::ALL),
+            vec![player.identity()]
+        );
+
+        let points = HashSet::from([Point::new(4, 100), Point::new(50, 50)]);
+        assert_eq!(
+            objects_overlapping(&objects, &points, CollisionLayers::ALL),
+            vec![boundary.identity()]
+        );
+        assert!(objects_overlapping(&objects, &HashSet::new(), CollisionLayers::ALL).is_empty());
+    }
+}
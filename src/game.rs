@@ -1,15 +1,23 @@
+use crate::collision::{self, CollisionEvent, CollisionLayers, CollisionMatrix, RaycastHit};
 use crate::game_objects::*;
+use crate::particles::{ParticleEmitter, ParticleSystem};
 use crate::structs::*;
 use crate::traits::*;
-use crate::util::has_collided;
-use rand::Rng;
-use sdl2::render::WindowCanvas;
-use std::collections::{HashMap, HashSet};
+use crate::ui::{Button, Hud, HudStats, Menu, Slider, TextField, UiTheme, WidgetEvent};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
 
 const FRAME_RATE: u8 = 60;
 const MAX_FIRE_RATE_PER_SEC: u8 = 7;
 const MIN_FRAMES_BETWEEN_SHOTS: u8 = FRAME_RATE / MAX_FIRE_RATE_PER_SEC;
 const FREE_ROME_ENABLED: bool = true;
+const PILOT_NAME_MAX_LENGTH: usize = 8;
+
+// Ids of the pause menu widgets
+const PAUSE_MENU_RESUME: &str = "resume";
+const PAUSE_MENU_SPEED: &str = "speed";
+const PAUSE_MENU_PILOT: &str = "pilot";
 
 struct GameMapDimensions {
     pub width: u32,
@@ -27,6 +35,10 @@ impl GameMapDimensions {
             origin: Point::new(0, 0),
         }
     }
+
+    pub fn dimensions(&self) -> Dimensions {
+        Dimensions::new(self.width, self.height)
+    }
 }
 
 ///
@@ -39,19 +51,64 @@ pub struct Game {
     player: Player,
     // Game world boundary
     boundary: Boundary,
+    // Which object types collide, and how the game responds
+    collisions: CollisionMatrix<Game>,
+    // Objects removed by collision handlers during the current pass
+    removed_objects: HashSet<(GameObjectType, u32)>,
     // Coins that exist in the world
     coins: Vec<Coin>,
-    projectiles: Vec<Projectile>,
+    // Projectiles in flight, keyed by id. Destroyed projectiles are reused for later shots
+    projectiles: ObjectPool<Projectile>,
+    // Visual-only effects, drawn on the effects layer and ignored by collision
+    particles: ParticleSystem,
+    // View onto the map, following the player
+    camera: Camera,
+    // Drawn over the arena in screen space
+    hud: Hud,
+    // Shown while the game is paused; `None` while playing
+    pause_menu: Option<Menu>,
+    // Without a font the pause menu is not drawn, but pausing still works
+    ui_theme: Option<UiTheme>,
+    // Entered in the pause menu, kept so the field shows it again the next time
+    pilot_name: String,
+    // Ticks since the match started
+    elapsed_frames: u64,
+    // Seed of `rng`, so that a match can be replayed
+    seed: u64,
+    rng: StdRng,
 }
 
 impl Game {
     pub fn init(map_width: u32, map_height: u32) -> Self {
+        Game::init_with_seed(map_width, map_height, rand::random())
+    }
+
+    pub fn init_with_seed(map_width: u32, map_height: u32, seed: u64) -> Self {
         // Create map dimensions
         let map_dimensions = GameMapDimensions::new(map_width, map_height);
 
         // Create boundaries
         let boundary = Boundary::new(map_dimensions.width, map_dimensions.height);
 
+        // Register collision pairs. The first type of each pair is the one swept along its path
+        let mut collisions = CollisionMatrix::new()
+            .with_pair(
+                GameObjectType::Player,
+                GameObjectType::Boundary,
+                Game::on_player_hit_boundary,
+            )
+            .with_pair(
+                GameObjectType::Projectile,
+                GameObjectType::Boundary,
+                Game::on_projectile_hit_boundary,
+            )
+            .with_pair(
+                GameObjectType::Projectile,
+                GameObjectType::Coin,
+                Game::on_projectile_hit_coin,
+            );
+        collisions.add_static(&boundary);
+
         // Create player
         let mut player = Player::new(Point::new(
             (map_dimensions.width / 2) as i32,
@@ -68,16 +125,146 @@ impl Game {
         let coin_origin = Point::new(map_dimensions.origin.x + 100, map_dimensions.origin.y + 100);
         let coins = vec![Coin::new(coin_origin)];
 
+        // Show the whole map until a viewport is set
+        let mut camera = Camera::new(map_dimensions.dimensions());
+        camera.follow(player.origin(), map_dimensions.dimensions());
+
         Self {
             map_dimensions,
             boundary,
+            collisions,
+            removed_objects: HashSet::new(),
             player,
             coins,
-            projectiles: Vec::new(),
+            projectiles: ObjectPool::new(),
+            particles: ParticleSystem::new(seed),
+            camera,
+            hud: Hud::new(),
+            pause_menu: None,
+            // The HUD already reports a font that fails to load
+            ui_theme: UiTheme::new().ok(),
+            pilot_name: String::new(),
+            elapsed_frames: 0,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn map_dimensions(&self) -> Dimensions {
+        self.map_dimensions.dimensions()
+    }
+
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    pub fn hud_mut(&mut self) -> &mut Hud {
+        &mut self.hud
+    }
+
+    pub fn hud_stats(&self) -> HudStats {
+        HudStats {
+            coins: self.player.coin_count(),
+            speed: self.player.speed(),
+            fire_readiness: self.player.frames_since_last_shot() as f32
+                / MIN_FRAMES_BETWEEN_SHOTS as f32,
+            elapsed_frames: self.elapsed_frames,
+            frame_rate: FRAME_RATE as u32,
         }
     }
 
+    /// Whether a text widget has focus, so typed characters should be sent as
+    /// `PlayerInput::TextInput`
+    pub fn wants_text_input(&self) -> bool {
+        self.pause_menu
+            .as_ref()
+            .is_some_and(|menu| menu.captures_text())
+    }
+
+    pub fn game_object(
+        &self,
+        (game_object_type, id): (GameObjectType, u32),
+    ) -> Option<&dyn GameObject> {
+        // Projectiles can be numerous, so they are looked up by id instead of searched
+        if game_object_type == GameObjectType::Projectile {
+            return self
+                .projectiles
+                .get(id)
+                .map(|projectile| projectile as &dyn GameObject);
+        }
+
+        self.all_game_objects()
+            .into_iter()
+            .find(|object| object.identity() == (game_object_type, id))
+    }
+
+    /// First object on `layers` hit by a ray from `origin` in `direction`, within
+    /// `max_distance` pixels
+    pub fn raycast(
+        &self,
+        origin: Point,
+        direction: (f32, f32),
+        max_distance: u32,
+        layers: CollisionLayers,
+    ) -> Option<RaycastHit> {
+        collision::raycast(
+            &self.all_game_objects(),
+            origin,
+            direction,
+            max_distance,
+            layers,
+        )
+    }
+
+    /// Objects on `layers` that are solid at `point`
+    pub fn objects_at(&self, point: Point, layers: CollisionLayers) -> Vec<(GameObjectType, u32)> {
+        collision::objects_at(&self.all_game_objects(), point, layers)
+    }
+
+    /// Objects on `layers` that are solid anywhere inside `bounds`
+    pub fn objects_in_rect(
+        &self,
+        bounds: BoundingBox,
+        layers: CollisionLayers,
+    ) -> Vec<(GameObjectType, u32)> {
+        collision::objects_in_rect(&self.all_game_objects(), &bounds, layers)
+    }
+
+    /// Objects on `layers` that the collision points of `sprite` would touch if it were placed
+    /// at `origin`, e.g. to check that a spawn point is free
+    pub fn objects_overlapping_sprite(
+        &self,
+        sprite: &Sprite,
+        origin: Point,
+        layers: CollisionLayers,
+    ) -> Vec<(GameObjectType, u32)> {
+        collision::objects_overlapping(
+            &self.all_game_objects(),
+            &Footprint::new(sprite, origin).collision_points().collect(),
+            layers,
+        )
+    }
+
+    /// Sets the size of the area the game is rendered to, which may be smaller than the map.
+    pub fn set_viewport(&mut self, width: u32, height: u32) {
+        self.camera.set_viewport(Dimensions::new(width, height));
+        self.camera
+            .follow(self.player.origin(), self.map_dimensions.dimensions());
+    }
+
     pub fn tick(&mut self, event: Option<PlayerInput>) {
+        // The world stands still while the pause menu is open
+        if self.pause_menu.is_some() {
+            if let Some(input) = event {
+                self.handle_pause_menu_input(input);
+            }
+            return;
+        }
+
         //////// Input //////
         if event.is_some() {
             match event.unwrap() {
@@ -92,9 +279,11 @@ impl Game {
                     Key::Num4 => self.player.change_speed(4),
                     Key::Num5 => self.player.change_speed(5),
                     Key::SpaceBar => self.fire_projectile(),
+                    Key::Pause => self.open_pause_menu(),
+                    Key::Up | Key::Down | Key::Left | Key::Right | Key::Enter | Key::Backspace => {}
                 },
+                PlayerInput::TextInput(_) => {}
             }
-            println!("Player direction: {:?}", self.player.direction());
         }
 
         ////// Game Logic //////
@@ -102,110 +291,142 @@ impl Game {
         //// Tick GameObjects
         self.player.tick();
         self.projectiles.iter_mut().for_each(|mut p| p.tick());
+        self.particles.tick();
 
         //// Handle collisions
-        // If player collides with boundary/wall, return to original position
-        self.handle_collisions_with_boundary();
-
-        // If projectile collides with boundary, destroy it
-        self.handle_collisions_projectile_and_boundary();
-
-        // If player projectile collides with coin, "collect" coin
-        self.handle_collisions_projectiles_and_coins();
+        self.handle_collisions();
 
         //// Additional events
         // Spawn coin if no other coin exists
         if self.coins.is_empty() {
-            let mut rng = rand::thread_rng();
-            let x = rng.gen_range(0..self.map_dimensions.width) as i32;
-            let y = rng.gen_range(0..self.map_dimensions.height) as i32;
+            let x = self.rng.random_range(0..self.map_dimensions.width) as i32;
+            let y = self.rng.random_range(0..self.map_dimensions.height) as i32;
 
             self.coins.push(Coin::new(Point::new(x, y)));
         }
+
+        //// Camera
+        self.camera
+            .follow(self.player.origin(), self.map_dimensions.dimensions());
+
+        self.elapsed_frames += 1;
     }
 
-    pub fn render(&self, canvas: &mut WindowCanvas) {
-        fn render_map(pixels: &HashMap<Point, Pixel>, canvas: &mut WindowCanvas) {
-            // Try multi-threading this?
-            for (point, pixel) in pixels {
-                let color = pixel.color;
-                canvas.set_draw_color(sdl2::pixels::Color::RGBA(
-                    color.r, color.g, color.b, color.a,
-                ));
+    pub fn render(&self, renderer: &mut dyn Renderer) {
+        let mut game_objects = self.all_game_objects();
+        // Stable, so objects on the same layer and z-index keep the order they were added in
+        game_objects.sort_by_key(|obj| (obj.render_layer(), obj.z_index()));
 
-                let canvas_point = sdl2::rect::Point::new(point.x as i32, point.y as i32);
-                canvas.draw_point(canvas_point).unwrap();
-            }
-        }
+        // Particles are not game objects, so they are slotted in before the first object drawn
+        // above the effects layer
+        let (below_effects, above_effects): (Vec<_>, Vec<_>) = game_objects
+            .into_iter()
+            .partition(|obj| obj.render_layer() <= RenderLayer::Effects);
 
-        self.all_game_objects()
+        below_effects
             .iter()
-            .for_each(|obj| render_map(obj.effective_pixels(), canvas))
-    }
+            .for_each(|obj| self.render_object(renderer, *obj));
+        self.particles.render(renderer, &self.camera);
+        above_effects
+            .iter()
+            .for_each(|obj| self.render_object(renderer, *obj));
 
-    fn handle_collisions_with_boundary(&mut self) {
-        // Check for player and boundary collisions
-        for player_point in self.player.effective_points() {
-            // If player collides with boundary, set the player's origin to it's previous position
-            if self.boundary.sprite().pixels().contains_key(player_point) {
-                let prev_player_origin = self.player.prev_origin_unchecked();
-                self.player.set_origin(prev_player_origin);
-                break;
-            }
+        // The HUD sits above every world layer, including UI objects in the world
+        self.hud
+            .render(renderer, self.camera.viewport(), &self.hud_stats());
+
+        if let (Some(menu), Some(theme)) = (&self.pause_menu, &self.ui_theme) {
+            menu.render(renderer, theme, self.camera.viewport());
         }
     }
 
-    fn handle_collisions_projectile_and_boundary(&mut self) {
-        fn did_projectile_collide_with_boundary(
-            boundary_points: &HashMap<Point, Pixel>,
-            projectile: &Projectile,
-        ) -> Option<u32> {
-            for proj_point in projectile.effective_points() {
-                if boundary_points.contains_key(proj_point) {
-                    return Some(projectile.id());
-                }
+    fn open_pause_menu(&mut self) {
+        self.pause_menu = Some(
+            Menu::new("PAUSED")
+                .with_widget(PAUSE_MENU_RESUME, Button::new("RESUME"))
+                .with_widget(
+                    PAUSE_MENU_SPEED,
+                    Slider::new("SPEED", 1, 5, 1).with_value(self.player.speed() as i32),
+                )
+                .with_widget(
+                    PAUSE_MENU_PILOT,
+                    TextField::new("PILOT", PILOT_NAME_MAX_LENGTH).with_text(&self.pilot_name),
+                ),
+        );
+    }
+
+    fn handle_pause_menu_input(&mut self, input: PlayerInput) {
+        let menu = match &mut self.pause_menu {
+            Some(menu) => menu,
+            None => return,
+        };
+
+        // The pause key resumes, unless it is being typed into the pilot name
+        if input == PlayerInput::KeyDown(Key::Pause) && !menu.captures_text() {
+            self.pause_menu = None;
+            return;
+        }
+
+        let menu_event = match menu.handle_input(input) {
+            Some(menu_event) => menu_event,
+            None => return,
+        };
+        match (menu_event.id.as_str(), menu_event.event) {
+            (PAUSE_MENU_RESUME, WidgetEvent::Activated) => self.pause_menu = None,
+            (PAUSE_MENU_SPEED, WidgetEvent::ValueChanged(speed)) => {
+                self.player.change_speed(speed as u32)
+            }
+            (PAUSE_MENU_PILOT, WidgetEvent::TextChanged(name) | WidgetEvent::Submitted(name)) => {
+                self.pilot_name = name
             }
-            None
+            _ => {}
         }
+    }
 
-        let projectiles_to_destroy: Vec<u32> = self
-            .projectiles
-            .iter()
-            .map(|p| did_projectile_collide_with_boundary(self.boundary.effective_pixels(), p))
-            .filter(|p_id| p_id.is_some())
-            .map(|p_id| p_id.unwrap())
-            .collect();
+    fn render_object(&self, renderer: &mut dyn Renderer, obj: &dyn GameObject) {
+        obj.draw(renderer, self.camera.world_to_screen(obj.origin()))
+    }
 
-        for projectile_id in projectiles_to_destroy {
-            self.destroy_projectile(projectile_id);
+    /// Finds every collision of the registered pairs and dispatches them to their handlers
+    fn handle_collisions(&mut self) {
+        let collisions = self.collisions.detect(&self.all_game_objects());
+        self.removed_objects.clear();
+
+        for collision in collisions {
+            // An earlier handler this pass may have removed either object, e.g. a projectile
+            // that hit the boundary before reaching a coin further along its path
+            if self.removed_objects.contains(&collision.first)
+                || self.removed_objects.contains(&collision.second)
+            {
+                continue;
+            }
+            if let Some(handler) = self.collisions.handler(collision.pair()) {
+                handler(self, &collision);
+            }
         }
     }
 
-    fn handle_collisions_projectiles_and_coins(&mut self) {
-        // It doesn't matter what projectile collided with a coin. So we should just loop through
-        // the projectile list once and create a single HashSet containing all projectile effective
-        // points. This allows each coin to check for collision using a O(n) operation (instead of O(n * m))
-        let mut all_projectile_points: HashSet<Point> = HashSet::new();
-        &self
-            .projectiles
-            .iter()
-            .flat_map(|projectile| projectile.effective_points())
-            .for_each(|point| {
-                all_projectile_points.insert(*point);
-            });
-
-        let coins_to_collect: Vec<u32> = self
-            .coins
-            .iter()
-            .filter(|coin| has_collided(coin.effective_points(), &all_projectile_points))
-            .map(|coin| coin.id())
-            .collect();
+    fn on_player_hit_boundary(&mut self, _collision: &CollisionEvent) {
+        // Move the player as far as it can go and let it slide along the wall on the axis that
+        // is still free
+        if let Some(resolved_origin) = self.player.slide_against(&self.boundary) {
+            self.player.set_origin(resolved_origin);
+        }
+    }
 
-        for coin_id in coins_to_collect {
-            self.collect_coin(coin_id)
+    fn on_projectile_hit_boundary(&mut self, collision: &CollisionEvent) {
+        let (_, projectile_id) = collision.first;
+        if self.destroy_projectile(projectile_id) {
+            self.particles
+                .emit(&ParticleEmitter::impact(), collision.hit.contact_point);
         }
     }
 
+    fn on_projectile_hit_coin(&mut self, collision: &CollisionEvent) {
+        let (_, coin_id) = collision.second;
+        self.collect_coin(coin_id);
+    }
+
     fn fire_projectile(&mut self) {
         if MIN_FRAMES_BETWEEN_SHOTS as u32 > self.player.frames_since_last_shot() {
             return;
@@ -218,36 +439,47 @@ impl Game {
             Rotation::Left => Direction::Left,
         };
 
-        self.projectiles.push(Projectile::new(
-            self.player.origin(),
-            projectile_direction,
-            self.player.rotation(),
-        ));
+        let projectile = match self.projectiles.recycle() {
+            Some(mut projectile) => {
+                projectile.reset(
+                    self.player.origin(),
+                    projectile_direction,
+                    self.player.rotation(),
+                );
+                projectile
+            }
+            None => Projectile::new(
+                self.player.origin(),
+                projectile_direction,
+                self.player.rotation(),
+            ),
+        };
+        self.projectiles.insert(projectile.id(), projectile);
         self.player.reset_frames_since_last_shot();
     }
 
     fn collect_coin(&mut self, coin_id: u32) {
-        println!("Collected coin: {}", coin_id);
-        let index_opt = self
-            .coins
-            .iter()
-            .position(|coin| coin.id() == coin_id)
-            .expect(format!("Coin (ID: {}) not found", coin_id).as_str());
+        // Several projectiles can hit the same coin in one tick
+        let index_opt = match self.coins.iter().position(|coin| coin.id() == coin_id) {
+            Some(index) => index,
+            None => return,
+        };
 
-        self.coins.remove(index_opt);
+        let coin = self.coins.remove(index_opt);
+        self.removed_objects.insert(coin.identity());
+        self.particles
+            .emit(&ParticleEmitter::coin_pickup(), coin.origin());
         self.player.increment_coin_count();
-
-        println!("Player coin count: {}", self.player.coin_count());
     }
 
-    fn destroy_projectile(&mut self, projectile_id: u32) {
-        let index_opt = self
-            .projectiles
-            .iter()
-            .position(|projectile| projectile.id() == projectile_id)
-            .expect(format!("Projectile (ID: {}) not found", projectile_id).as_str());
-
-        self.projectiles.remove(index_opt);
+    /// Returns whether the projectile still existed
+    fn destroy_projectile(&mut self, projectile_id: u32) -> bool {
+        let destroyed = self.projectiles.remove(projectile_id);
+        if destroyed {
+            self.removed_objects
+                .insert((GameObjectType::Projectile, projectile_id));
+        }
+        destroyed
     }
 
     fn all_game_objects(&self) -> Vec<&dyn GameObject> {
@@ -263,3 +495,55 @@ impl Game {
         all_game_objects
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projectile_collects_coin_before_hitting_wall() {
+        let mut game = Game::init_with_seed(200, 200, 1);
+        game.coins = vec![Coin::new(Point::new(60, 60))];
+        let mut projectile = Projectile::new(Point::new(60, 150), Direction::Up, Rotation::Up);
+        projectile.set_origin(Point::new(60, -10));
+        let projectile_id = projectile.id();
+        game.projectiles.insert(projectile_id, projectile);
+
+        game.handle_collisions();
+
+        assert!(!game.projectiles.contains(projectile_id));
+        assert!(game.coins.is_empty());
+        assert_eq!(game.player.coin_count(), 1);
+    }
+
+    #[test]
+    fn projectile_destroyed_by_wall_skips_coin_behind_it() {
+        let mut game = Game::init_with_seed(200, 200, 1);
+        // Just beyond the top wall, so the projectile reaches the wall first
+        game.coins = vec![Coin::new(Point::new(60, -10))];
+        let mut projectile = Projectile::new(Point::new(60, 150), Direction::Up, Rotation::Up);
+        projectile.set_origin(Point::new(60, -30));
+        let projectile_id = projectile.id();
+        game.projectiles.insert(projectile_id, projectile);
+
+        game.handle_collisions();
+
+        assert!(!game.projectiles.contains(projectile_id));
+        assert_eq!(game.coins.len(), 1);
+        assert_eq!(game.player.coin_count(), 0);
+    }
+
+    #[test]
+    fn player_stops_flush_against_wall() {
+        let mut game = Game::init_with_seed(200, 200, 1);
+        game.player.change_speed(5);
+        game.player.change_direction(Direction::Down);
+
+        for _ in 0..30 {
+            game.tick(None);
+        }
+
+        let bottom = game.player.footprint().collision_bounds().unwrap().max.y;
+        assert_eq!(bottom, 200 - 5 - 1);
+    }
+}
@@ -1,10 +1,10 @@
-use std::collections::{HashMap, HashSet};
+use std::cell::OnceCell;
 
 use crate::structs::*;
-use crate::traits::GameObject;
-use crate::util::calc_effective_sprite_pixels;
+use crate::traits::{GameObject, Renderer};
 
-static BOUNDARY_WIDTH: usize = 5;
+pub const BOUNDARY_SPRITE_NAME: &str = "boundary";
+static BOUNDARY_WIDTH: u32 = 5;
 static BOUNDARY_COLOR: Color = Color::WHITE;
 static mut ID_COUNTER: u32 = 0;
 
@@ -12,28 +12,33 @@ pub struct Boundary {
     id: u32,
     game_object_type: GameObjectType,
     origin: Point,
-    sprite: Sprite,
-    effective_sprite_pixels: HashMap<Point, Pixel>,
-    effective_sprite_points: HashSet<Point>,
+    terrain: TerrainBitmap,
+    // Terrain merged into filled rectangles, so drawing does not touch every pixel
+    terrain_rects: Vec<(Point, Dimensions, Color)>,
+    terrain_bounds: Option<BoundingBox>,
+    // Per-pixel view of the terrain, only built if something asks for it
+    sprite: OnceCell<Sprite>,
 }
 
 impl Boundary {
     pub fn new(map_width: u32, map_height: u32) -> Self {
-        let origin = Point::new(0, 0);
-        let sprite = Sprite::new(Boundary::get_boundary_shape(map_width, map_height));
-        let (effective_sprite_pixels, effective_sprite_points) =
-            calc_effective_sprite_pixels(&sprite, origin);
+        let terrain = Boundary::get_boundary_terrain(map_width, map_height);
 
         Boundary {
             id: Boundary::get_id(),
             game_object_type: GameObjectType::Boundary,
-            origin,
-            sprite,
-            effective_sprite_pixels,
-            effective_sprite_points,
+            origin: Point::new(0, 0),
+            terrain_rects: terrain.rects(),
+            terrain_bounds: terrain.bounds(),
+            terrain,
+            sprite: OnceCell::new(),
         }
     }
 
+    pub fn terrain(&self) -> &TerrainBitmap {
+        &self.terrain
+    }
+
     fn get_id() -> u32 {
         let id: u32;
         unsafe {
@@ -44,55 +49,33 @@ impl Boundary {
         id
     }
 
-    fn get_boundary_shape(map_width: u32, map_height: u32) -> HashMap<Point, Pixel> {
-        // Base 1d array
-        let mut base_shape_data = vec![None; map_height as usize * map_width as usize];
-
-        // Vector of 'width' elements slices
-        let mut grid_base: Vec<_> = base_shape_data
-            .as_mut_slice()
-            .chunks_mut(map_width as usize)
-            .collect();
-
-        // Final 2d array `&mut [&mut [_]]`
-        let shape_data_grid = grid_base.as_mut_slice();
-
-        // Fill top and bottom boundary
-        for x in 0..(map_width as usize) {
-            for y in 0..BOUNDARY_WIDTH {
-                // Top boundary
-                shape_data_grid[y][x] = Some(BOUNDARY_COLOR.clone());
-
-                // Bottom boundary
-                shape_data_grid[(y + map_height as usize) - BOUNDARY_WIDTH][x] =
-                    Some(BOUNDARY_COLOR.clone());
-            }
-        }
-
-        // Fill left and right boundary
-        for y in 0..(map_height as usize) {
-            for x in 0..BOUNDARY_WIDTH {
-                // Left boundary
-                shape_data_grid[y][x] = Some(BOUNDARY_COLOR.clone());
-
-                // Right boundary
-                shape_data_grid[y][(x + map_width as usize) - 5] = Some(BOUNDARY_COLOR.clone());
-            }
-        }
-
-        // Convert 2D Vector to HashMap
-        let mut pixels: HashMap<Point, Pixel> = HashMap::new();
-        for y in 0..shape_data_grid.len() {
-            for x in 0..shape_data_grid[y].len() {
-                let color = shape_data_grid[y][x];
-                if color.is_some() {
-                    let location = Point::new(x as i32, y as i32);
-                    pixels.insert(location.clone(), Pixel::new(color.unwrap()));
-                }
-            }
-        }
+    fn get_boundary_terrain(map_width: u32, map_height: u32) -> TerrainBitmap {
+        let mut terrain =
+            TerrainBitmap::new(Dimensions::new(map_width, map_height), BOUNDARY_COLOR);
+        let horizontal = Dimensions::new(map_width, BOUNDARY_WIDTH);
+        let vertical = Dimensions::new(BOUNDARY_WIDTH, map_height);
+
+        // Top and bottom boundary
+        terrain.fill_rect(Point::new(0, 0), horizontal, BOUNDARY_COLOR);
+        terrain.fill_rect(
+            Point::new(0, map_height as i32 - BOUNDARY_WIDTH as i32),
+            horizontal,
+            BOUNDARY_COLOR,
+        );
+
+        // Left and right boundary
+        terrain.fill_rect(Point::new(0, 0), vertical, BOUNDARY_COLOR);
+        terrain.fill_rect(
+            Point::new(map_width as i32 - BOUNDARY_WIDTH as i32, 0),
+            vertical,
+            BOUNDARY_COLOR,
+        );
+
+        terrain
+    }
 
-        pixels
+    fn to_terrain_point(&self, point: Point) -> Point {
+        Point::new(point.x - self.origin.x, point.y - self.origin.y)
     }
 }
 
@@ -101,6 +84,10 @@ impl GameObject for Boundary {
         self.game_object_type
     }
 
+    fn render_layer(&self) -> RenderLayer {
+        RenderLayer::Terrain
+    }
+
     fn id(&self) -> u32 {
         self.id
     }
@@ -114,18 +101,53 @@ impl GameObject for Boundary {
     }
 
     fn sprite(&self) -> &Sprite {
-        &self.sprite
+        self.sprite.get_or_init(|| {
+            let pixels = self
+                .terrain
+                .solid_points()
+                .map(|point| (point, Pixel::new(self.terrain.color(point).unwrap())));
+            Sprite::new(BOUNDARY_SPRITE_NAME, pixels)
+        })
     }
 
     fn sprite_dimensions(&self) -> Dimensions {
-        *self.sprite.dimensions()
+        self.terrain.dimensions()
+    }
+
+    fn is_solid_at(&self, point: Point) -> bool {
+        self.terrain.is_solid(self.to_terrain_point(point))
+    }
+
+    fn solid_points(&self) -> Box<dyn Iterator<Item = Point> + '_> {
+        let origin = self.origin;
+        Box::new(
+            self.terrain
+                .solid_points()
+                .map(move |point| Point::new(point.x + origin.x, point.y + origin.y)),
+        )
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, screen_origin: Point) {
+        let opacity = self.opacity();
+        for (origin, dimensions, color) in &self.terrain_rects {
+            renderer.fill_rect(
+                Point::new(screen_origin.x + origin.x, screen_origin.y + origin.y),
+                *dimensions,
+                Color {
+                    a: (color.a as f32 * opacity).round() as u8,
+                    ..*color
+                },
+            );
+        }
     }
 
-    fn effective_pixels(&self) -> &HashMap<Point, Pixel> {
-        &self.effective_sprite_pixels
+    fn collision_bounds(&self) -> Option<BoundingBox> {
+        self.terrain_bounds
+            .map(|bounds| bounds.translate(self.origin))
     }
 
-    fn effective_points(&self) -> &HashSet<Point> {
-        &self.effective_sprite_points
+    fn swept_collision_bounds(&self) -> Option<BoundingBox> {
+        // Terrain never moves
+        self.collision_bounds()
     }
 }
@@ -1,8 +1,5 @@
-use std::collections::{HashMap, HashSet};
-
 use crate::structs::*;
 use crate::traits::*;
-use crate::util::calc_effective_sprite_pixels;
 
 ///
 /// Coin definition
@@ -15,23 +12,17 @@ pub struct Coin {
     game_object_type: GameObjectType,
     origin: Point,
     sprite: Sprite,
-    effective_sprite_pixels: HashMap<Point, Pixel>,
-    effective_sprite_points: HashSet<Point>,
 }
 
 impl Coin {
     pub fn new(origin: Point) -> Self {
-        let sprite = Sprite::new_from_file(COIN_SPRITE_FILENAME);
-        let (effective_sprite_pixels, effective_sprite_points) =
-            calc_effective_sprite_pixels(&sprite, origin);
+        let sprite = Sprite::shared(COIN_SPRITE_FILENAME);
 
         Self {
             id: Coin::get_id(),
             game_object_type: GameObjectType::Coin,
             origin,
             sprite,
-            effective_sprite_pixels,
-            effective_sprite_points,
         }
     }
 
@@ -51,6 +42,10 @@ impl GameObject for Coin {
         self.game_object_type
     }
 
+    fn render_layer(&self) -> RenderLayer {
+        RenderLayer::Pickups
+    }
+
     fn id(&self) -> u32 {
         self.id
     }
@@ -70,14 +65,6 @@ impl GameObject for Coin {
     fn sprite_dimensions(&self) -> Dimensions {
         *self.sprite.dimensions()
     }
-
-    fn effective_points(&self) -> &HashSet<Point> {
-        &self.effective_sprite_points
-    }
-
-    fn effective_pixels(&self) -> &HashMap<Point, Pixel> {
-        &self.effective_sprite_pixels
-    }
 }
 
 impl Default for Coin {
@@ -1,8 +1,5 @@
-use std::collections::{HashMap, HashSet};
-
 use crate::structs::*;
 use crate::traits::*;
-use crate::util::calc_effective_sprite_pixels;
 
 ///
 /// Player definition
@@ -16,8 +13,6 @@ pub struct Player {
     origin: Point,
     prev_origin: Option<Point>,
     sprite: Sprite,
-    effective_sprite_pixels: HashMap<Point, Pixel>,
-    effective_sprite_points: HashSet<Point>,
     speed: u32,
     current_direction: Direction,
     current_rotation: Rotation,
@@ -28,17 +23,13 @@ pub struct Player {
 
 impl Player {
     pub fn new(origin: Point) -> Self {
-        let sprite = Sprite::new_from_file(PLAYER_SPRITE_FILENAME);
-        let (effective_sprite_pixels, effective_sprite_points) =
-            calc_effective_sprite_pixels(&sprite, origin);
+        let sprite = Sprite::shared(PLAYER_SPRITE_FILENAME);
         Player {
             id: Player::get_id(),
             game_object_type: GameObjectType::Player,
             origin,
             prev_origin: None,
             sprite,
-            effective_sprite_pixels,
-            effective_sprite_points,
             current_direction: Direction::Up,
             current_rotation: Rotation::Up,
             rotation_enabled: true,
@@ -62,7 +53,7 @@ impl Player {
         self.coin_count += 1;
     }
 
-    pub fn coin_count(&mut self) -> u32 {
+    pub fn coin_count(&self) -> u32 {
         self.coin_count
     }
 
@@ -86,28 +77,21 @@ impl GameObject for Player {
 
         self.apply_movement();
 
-        let mut something_changed = false;
-        if self.origin != self.prev_origin_unchecked() {
-            something_changed = true;
-        }
+        // Moving only changes the origin; the footprint follows on its own
         if self.current_rotation != prev_rotation {
-            something_changed = true;
             self.sprite
                 .rotate_sprite_around_origin(self.current_rotation);
         }
-
-        if something_changed {
-            let (effective_sprite_pixels, effective_sprite_points) =
-                calc_effective_sprite_pixels(&self.sprite(), self.origin);
-            self.effective_sprite_pixels = effective_sprite_pixels;
-            self.effective_sprite_points = effective_sprite_points;
-        }
     }
 
     fn game_object_type(&self) -> GameObjectType {
         self.game_object_type
     }
 
+    fn render_layer(&self) -> RenderLayer {
+        RenderLayer::Actors
+    }
+
     fn id(&self) -> u32 {
         self.id
     }
@@ -121,6 +105,10 @@ impl GameObject for Player {
         self.origin = new_origin;
     }
 
+    fn prev_origin(&self) -> Option<Point> {
+        self.prev_origin
+    }
+
     fn sprite(&self) -> &Sprite {
         &self.sprite
     }
@@ -128,14 +116,6 @@ impl GameObject for Player {
     fn sprite_dimensions(&self) -> Dimensions {
         *self.sprite.dimensions()
     }
-
-    fn effective_pixels(&self) -> &HashMap<Point, Pixel> {
-        &self.effective_sprite_pixels
-    }
-
-    fn effective_points(&self) -> &HashSet<Point> {
-        &self.effective_sprite_points
-    }
 }
 
 impl Default for Player {
@@ -177,13 +157,6 @@ impl Movable for Player {
         self.speed
     }
 
-    fn prev_origin(&self) -> Option<Point> {
-        match self.prev_origin {
-            Some(point) => Some(point),
-            None => None,
-        }
-    }
-
     fn set_prev_origin(&mut self, origin: Point) {
         self.prev_origin = Some(origin);
     }
@@ -1,8 +1,5 @@
-use crate::structs::{Dimensions, Direction, GameObjectType, Pixel, Point, Rotation, Sprite};
+use crate::structs::{Dimensions, Direction, GameObjectType, Point, RenderLayer, Rotation, Sprite};
 use crate::traits::{GameObject, Movable};
-use crate::util::calc_effective_sprite_pixels;
-use std::collections::{HashMap, HashSet};
-
 pub const PROJECTILE_SPRITE_FILENAME: &'static str = "projectile_sprite";
 static mut ID_COUNTER: u32 = 0;
 
@@ -10,9 +7,9 @@ pub struct Projectile {
     id: u32,
     game_object_type: GameObjectType,
     origin: Point,
+    // Origin before the last move, so collisions can be checked along the whole path
+    prev_origin: Option<Point>,
     sprite: Sprite,
-    effective_sprite_pixels: HashMap<Point, Pixel>,
-    effective_sprite_points: HashSet<Point>,
     direction: Direction,
     rotation: Rotation,
     rotation_enabled: bool,
@@ -21,19 +18,15 @@ pub struct Projectile {
 
 impl Projectile {
     pub fn new(origin: Point, direction: Direction, rotation: Rotation) -> Self {
-        let mut sprite = Sprite::new_from_file(PROJECTILE_SPRITE_FILENAME);
+        let mut sprite = Sprite::shared(PROJECTILE_SPRITE_FILENAME);
         sprite.rotate_sprite_around_origin(rotation);
 
-        let (effective_sprite_pixels, effective_sprite_points) =
-            calc_effective_sprite_pixels(&sprite, origin);
-
         Self {
             id: Self::id(),
             game_object_type: GameObjectType::Projectile,
             origin,
+            prev_origin: None,
             sprite,
-            effective_sprite_pixels,
-            effective_sprite_points,
             direction,
             rotation,
             rotation_enabled: false,
@@ -41,6 +34,16 @@ impl Projectile {
         }
     }
 
+    /// Reinitializes a projectile taken back out of a pool under a new id, keeping its sprite
+    pub fn reset(&mut self, origin: Point, direction: Direction, rotation: Rotation) {
+        self.id = Self::id();
+        self.origin = origin;
+        self.prev_origin = None;
+        self.sprite.rotate_sprite_around_origin(rotation);
+        self.direction = direction;
+        self.rotation = rotation;
+    }
+
     fn id() -> u32 {
         let id: u32;
         unsafe {
@@ -55,18 +58,16 @@ impl Projectile {
 impl GameObject for Projectile {
     fn tick(&mut self) {
         self.apply_movement();
-
-        let (effective_sprite_pixels, effective_sprite_points) =
-            calc_effective_sprite_pixels(&self.sprite, self.origin);
-
-        self.effective_sprite_pixels = effective_sprite_pixels;
-        self.effective_sprite_points = effective_sprite_points;
     }
 
     fn game_object_type(&self) -> GameObjectType {
         self.game_object_type
     }
 
+    fn render_layer(&self) -> RenderLayer {
+        RenderLayer::Projectiles
+    }
+
     fn id(&self) -> u32 {
         self.id
     }
@@ -76,9 +77,14 @@ impl GameObject for Projectile {
     }
 
     fn set_origin(&mut self, new_origin: Point) {
+        self.set_prev_origin(self.origin);
         self.origin = new_origin
     }
 
+    fn prev_origin(&self) -> Option<Point> {
+        self.prev_origin
+    }
+
     fn sprite(&self) -> &Sprite {
         &self.sprite
     }
@@ -86,14 +92,6 @@ impl GameObject for Projectile {
     fn sprite_dimensions(&self) -> Dimensions {
         *self.sprite.dimensions()
     }
-
-    fn effective_points(&self) -> &HashSet<Point> {
-        &self.effective_sprite_points
-    }
-
-    fn effective_pixels(&self) -> &HashMap<Point, Pixel> {
-        &self.effective_sprite_pixels
-    }
 }
 
 impl Movable for Projectile {
@@ -129,11 +127,7 @@ impl Movable for Projectile {
         self.speed
     }
 
-    fn prev_origin(&self) -> Option<Point> {
-        None
-    }
-
-    fn set_prev_origin(&mut self, _current_origin: Point) {
-        // Do nothing
+    fn set_prev_origin(&mut self, current_origin: Point) {
+        self.prev_origin = Some(current_origin);
     }
 }
@@ -0,0 +1,3 @@
+mod terminal_input;
+
+pub use terminal_input::*;
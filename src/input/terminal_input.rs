@@ -0,0 +1,99 @@
+use crate::structs::{Key, PlayerInput};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::time::Duration;
+
+///
+/// TerminalEvent definition
+///
+#[derive(Debug)]
+pub enum TerminalEvent {
+    Quit,
+    Input(PlayerInput),
+}
+
+///
+/// TerminalInput definition
+///
+/// Reads key presses from the terminal (which must be in raw mode, e.g. via `TerminalRenderer`)
+/// and translates them into `PlayerInput` using the same bindings as the SDL window.
+pub struct TerminalInput {
+    // While set, printable characters are delivered as `PlayerInput::TextInput`
+    text_input: bool,
+}
+
+///
+/// TerminalInput implementation
+///
+impl TerminalInput {
+    pub fn new() -> Self {
+        Self { text_input: false }
+    }
+
+    /// Turns text entry on while a text widget has focus, and off again afterwards
+    pub fn set_text_input(&mut self, text_input: bool) {
+        self.text_input = text_input;
+    }
+
+    /// Returns every event that is pending, without blocking.
+    pub fn poll_events(&mut self) -> std::io::Result<Vec<TerminalEvent>> {
+        let mut events = Vec::new();
+        while crossterm::event::poll(Duration::ZERO)? {
+            if let Event::Key(key_event) = crossterm::event::read()? {
+                if let Some(event) = self.translate(key_event) {
+                    events.push(event);
+                }
+            }
+        }
+
+        Ok(events)
+    }
+
+    fn translate(&self, key_event: KeyEvent) -> Option<TerminalEvent> {
+        if key_event.kind == KeyEventKind::Release {
+            return None;
+        }
+
+        let key = match key_event.code {
+            KeyCode::Esc => return Some(TerminalEvent::Quit),
+            KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(TerminalEvent::Quit)
+            }
+            KeyCode::Char(c) if self.text_input => {
+                return Some(TerminalEvent::Input(PlayerInput::TextInput(c)))
+            }
+            KeyCode::Char(c) => match c.to_ascii_lowercase() {
+                'w' => Key::W,
+                'a' => Key::A,
+                's' => Key::S,
+                'd' => Key::D,
+                'i' => Key::I,
+                'j' => Key::J,
+                'k' => Key::K,
+                'l' => Key::L,
+                '1' => Key::Num1,
+                '2' => Key::Num2,
+                '3' => Key::Num3,
+                '4' => Key::Num4,
+                '5' => Key::Num5,
+                ' ' => Key::SpaceBar,
+                'p' => Key::Pause,
+                _ => return None,
+            },
+            KeyCode::Up => Key::Up,
+            KeyCode::Down => Key::Down,
+            KeyCode::Left => Key::Left,
+            KeyCode::Right => Key::Right,
+            KeyCode::Enter => Key::Enter,
+            KeyCode::Backspace => Key::Backspace,
+            _ => return None,
+        };
+
+        Some(TerminalEvent::Input(PlayerInput::KeyDown(key)))
+    }
+}
+
+impl Default for TerminalInput {
+    fn default() -> Self {
+        TerminalInput::new()
+    }
+}
@@ -1,7 +1,12 @@
 extern crate core;
 
+pub mod collision;
 pub mod game;
 pub mod game_objects;
+pub mod input;
+pub mod particles;
+pub mod render;
 pub mod structs;
 pub mod traits;
+pub mod ui;
 pub mod util;
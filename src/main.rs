@@ -1,58 +1,269 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
-use sdl2::pixels::Color;
 
 use poly_battle::game::*;
+use poly_battle::input::*;
+use poly_battle::render::*;
 use poly_battle::structs::*;
+use poly_battle::traits::Renderer;
 use poly_battle::util::*;
 
 static GAME_TITLE: &'static str = "PolyBattle";
-static WINDOW_WIDTH: u32 = 800;
-static WINDOW_HEIGHT: u32 = 600;
+static LOGICAL_WIDTH: u32 = 800;
+static LOGICAL_HEIGHT: u32 = 600;
 
 ///
 /// ===== Main Program ======
 ///
 
 pub fn main() {
-    playground();
-    start();
+    let cli_args = parse_cli_args();
+    if let Some(asset_root) = &cli_args.asset_root {
+        set_asset_root(asset_root).unwrap();
+    }
+
+    match &cli_args.command {
+        Command::Play => {
+            playground();
+            if cli_args.terminal {
+                start_terminal(&cli_args);
+            } else {
+                start(&cli_args);
+            }
+        }
+        Command::LintAssets => lint_assets(),
+        Command::RecordReplay { replay, output } => {
+            record_replay(replay, output, cli_args.recording_options)
+        }
+    }
+}
+
+pub struct CliArgs {
+    command: Command,
+    asset_root: Option<PathBuf>,
+    terminal: bool,
+    map_size: Dimensions,
+    window_size: Option<Dimensions>,
+    scaling_mode: ScalingMode,
+    fullscreen: bool,
+    record: Option<PathBuf>,
+    save_replay: Option<PathBuf>,
+    recording_options: RecordingOptions,
+}
+
+pub enum Command {
+    Play,
+    LintAssets,
+    RecordReplay { replay: PathBuf, output: PathBuf },
+}
+
+fn parse_cli_args() -> CliArgs {
+    let mut cli_args = CliArgs {
+        command: Command::Play,
+        asset_root: None,
+        terminal: false,
+        map_size: Dimensions::new(LOGICAL_WIDTH, LOGICAL_HEIGHT),
+        window_size: None,
+        scaling_mode: ScalingMode::Integer,
+        fullscreen: false,
+        record: None,
+        save_replay: None,
+        recording_options: RecordingOptions::default(),
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "lint-assets" => cli_args.command = Command::LintAssets,
+            "record-replay" => {
+                cli_args.command = Command::RecordReplay {
+                    replay: PathBuf::from(next_value(&mut args, "record-replay")),
+                    output: PathBuf::from(next_value(&mut args, "record-replay")),
+                }
+            }
+            "--terminal" => cli_args.terminal = true,
+            "--assets" => {
+                cli_args.asset_root = Some(PathBuf::from(next_value(&mut args, "--assets")))
+            }
+            "--map-size" => {
+                cli_args.map_size = parse_map_size(&next_value(&mut args, "--map-size"))
+                    .unwrap_or_else(|| {
+                        exit_with_usage("--map-size must look like 1600x1200, at least 100x100")
+                    })
+            }
+            "--window-size" => {
+                let window_size = parse_window_size(&next_value(&mut args, "--window-size"))
+                    .unwrap_or_else(|| {
+                        exit_with_usage("--window-size must look like 1600x1200, up to 16384x16384")
+                    });
+                cli_args.window_size = Some(window_size)
+            }
+            "--scaling" => {
+                cli_args.scaling_mode = match next_value(&mut args, "--scaling").as_str() {
+                    "integer" => ScalingMode::Integer,
+                    "letterbox" => ScalingMode::Letterbox,
+                    _ => exit_with_usage("--scaling must be 'integer' or 'letterbox'"),
+                }
+            }
+            "--fullscreen" => cli_args.fullscreen = true,
+            "--record" => cli_args.record = Some(PathBuf::from(next_value(&mut args, "--record"))),
+            "--save-replay" => {
+                cli_args.save_replay = Some(PathBuf::from(next_value(&mut args, "--save-replay")))
+            }
+            "--frame-skip" => {
+                cli_args.recording_options.frame_skip = next_value(&mut args, "--frame-skip")
+                    .parse()
+                    .unwrap_or_else(|_| exit_with_usage("--frame-skip must be a whole number"))
+            }
+            "--scale" => {
+                cli_args.recording_options.scale = next_value(&mut args, "--scale")
+                    .parse()
+                    .ok()
+                    .filter(|scale: &f32| *scale > 0.0)
+                    .unwrap_or_else(|| exit_with_usage("--scale must be a positive number"))
+            }
+            _ => exit_with_usage(format!("Unrecognized argument: {}", arg).as_str()),
+        }
+    }
+
+    cli_args
+}
+
+/// Parses a size such as `1600x1200`
+fn parse_size(value: &str) -> Option<Dimensions> {
+    let (width, height) = value.split_once('x')?;
+    Some(Dimensions::new(width.parse().ok()?, height.parse().ok()?))
+}
+
+fn parse_map_size(value: &str) -> Option<Dimensions> {
+    let dimensions = parse_size(value)?;
+
+    // The player spawns 50 pixels above the bottom of the map
+    if dimensions.width < 100 || dimensions.height < 100 {
+        return None;
+    }
+    Some(dimensions)
+}
+
+fn parse_window_size(value: &str) -> Option<Dimensions> {
+    let dimensions = parse_size(value)?;
+
+    // SDL refuses to create empty windows and windows larger than 16384 pixels on either side
+    let valid_length = 1..=16384;
+    if !valid_length.contains(&dimensions.width) || !valid_length.contains(&dimensions.height) {
+        return None;
+    }
+    Some(dimensions)
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> String {
+    args.next()
+        .unwrap_or_else(|| exit_with_usage(format!("{} requires a value", flag).as_str()))
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("Usage: {} [command] [options]", GAME_TITLE);
+    eprintln!("Commands:");
+    eprintln!("    lint-assets                      Check every sprite in the asset root for inconsistencies");
+    eprintln!(
+        "    record-replay <replay> <output>  Render a saved replay to <output> without a window"
+    );
+    eprintln!("Options:");
+    eprintln!(
+        "    --assets <dir>                   Asset root directory (default: ${} or ./{})",
+        ASSET_ROOT_ENV_VAR, DEFAULT_ASSET_ROOT
+    );
+    eprintln!("    --terminal                       Play in the terminal instead of an SDL window");
+    eprintln!(
+        "    --map-size <width>x<height>      Size of the arena (default: the logical resolution)"
+    );
+    eprintln!(
+        "    --window-size <width>x<height>   Initial window size (default: fits the display)"
+    );
+    eprintln!("    --scaling <integer|letterbox>    How the game is scaled to the window (default: integer)");
+    eprintln!("    --fullscreen                     Start in fullscreen. F11 toggles fullscreen while playing");
+    eprintln!(
+        "    --record <output>                Record frames to a .gif file or a directory of PNGs"
+    );
+    eprintln!(
+        "    --save-replay <file>             Save the match's inputs so it can be recorded later"
+    );
+    eprintln!(
+        "    --frame-skip <n>                 Frames to skip between recorded frames (default: 0)"
+    );
+    eprintln!("    --scale <factor>                 Scale applied to recorded frames (default: 1)");
+    std::process::exit(2);
 }
 
 pub fn playground() {}
 
-pub fn start() {
+pub fn lint_assets() {
+    let dir = asset_root().to_path_buf();
+    let issues = lint_sprite_dir(&dir);
+
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    println!("{} issue(s) found in {}", issues.len(), dir.display());
+
+    if !issues.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+pub fn start(cli_args: &CliArgs) {
     let sdl_context = sdl2::init().unwrap();
     let video_subsystem = sdl_context.video().unwrap();
 
+    let window_size = cli_args
+        .window_size
+        .unwrap_or_else(|| default_window_size(&video_subsystem));
     let window = video_subsystem
-        .window(GAME_TITLE, WINDOW_WIDTH, WINDOW_HEIGHT)
+        .window(GAME_TITLE, window_size.width, window_size.height)
         .position_centered()
+        .resizable()
         .build()
         .unwrap();
 
-    let mut canvas = window.into_canvas().present_vsync().build().unwrap();
+    let canvas = window.into_canvas().present_vsync().build().unwrap();
+    let texture_creator = canvas.texture_creator();
+    let mut renderer = SdlRenderer::new(canvas, &texture_creator);
+    renderer.set_logical_size(LOGICAL_WIDTH, LOGICAL_HEIGHT, cli_args.scaling_mode);
+    renderer.set_fullscreen(cli_args.fullscreen);
     let mut event_pump = sdl_context.event_pump().unwrap();
+    let text_input = video_subsystem.text_input();
+    // SDL starts with text input enabled; it is only wanted while a text widget has focus
+    text_input.stop();
 
-    let mut game_state = Game::init(WINDOW_WIDTH, WINDOW_HEIGHT);
+    let mut game_state = Game::init(cli_args.map_size.width, cli_args.map_size.height);
+    game_state.set_viewport(LOGICAL_WIDTH, LOGICAL_HEIGHT);
+    let mut match_capture = MatchCapture::new(cli_args, &game_state);
 
     let fps = 60;
     let frame_duration = 1_000_000_000u32 / fps;
+    // The game takes one input per tick, so inputs arriving in the same frame wait their turn
+    let mut pending_inputs: VecDeque<PlayerInput> = VecDeque::new();
 
     'running: loop {
         let mut performance_tracker = PerformanceTracker::new();
 
         // Set background
         performance_tracker.measure_unit_of_work("draw_background", || {
-            canvas.set_draw_color(Color::BLACK);
-            canvas.clear();
+            renderer.clear(Color::BLACK);
         });
 
         // Check for events
         performance_tracker.start_unit_of_work("get_input");
-        let mut player_input: Option<PlayerInput> = None;
+        match game_state.wants_text_input() {
+            true if !text_input.is_active() => text_input.start(),
+            false if text_input.is_active() => text_input.stop(),
+            _ => {}
+        }
         for event in event_pump.poll_iter() {
             match event {
                 Event::Quit { .. }
@@ -62,29 +273,28 @@ pub fn start() {
                 } => {
                     break 'running;
                 }
+                Event::KeyDown {
+                    keycode: Some(Keycode::F11),
+                    ..
+                } => renderer.toggle_fullscreen(),
+                // Typed text, only delivered while a text widget has focus
+                Event::TextInput { text, .. } => {
+                    pending_inputs.extend(text.chars().map(PlayerInput::TextInput))
+                }
                 // Player controls
                 Event::KeyDown {
-                    keycode: Some(key), ..
-                } => match key {
-                    Keycode::W => player_input = Some(PlayerInput::KeyDown(Key::W)),
-                    Keycode::A => player_input = Some(PlayerInput::KeyDown(Key::A)),
-                    Keycode::S => player_input = Some(PlayerInput::KeyDown(Key::S)),
-                    Keycode::D => player_input = Some(PlayerInput::KeyDown(Key::D)),
-                    Keycode::I => player_input = Some(PlayerInput::KeyDown(Key::I)),
-                    Keycode::J => player_input = Some(PlayerInput::KeyDown(Key::J)),
-                    Keycode::K => player_input = Some(PlayerInput::KeyDown(Key::K)),
-                    Keycode::L => player_input = Some(PlayerInput::KeyDown(Key::L)),
-                    Keycode::Num1 => player_input = Some(PlayerInput::KeyDown(Key::Num1)),
-                    Keycode::Num2 => player_input = Some(PlayerInput::KeyDown(Key::Num2)),
-                    Keycode::Num3 => player_input = Some(PlayerInput::KeyDown(Key::Num3)),
-                    Keycode::Num4 => player_input = Some(PlayerInput::KeyDown(Key::Num4)),
-                    Keycode::Num5 => player_input = Some(PlayerInput::KeyDown(Key::Num5)),
-                    Keycode::Space => player_input = Some(PlayerInput::KeyDown(Key::SpaceBar)),
-                    _ => {}
+                    keycode: Some(keycode),
+                    ..
+                } => match sdl_key(keycode) {
+                    // Keys that type a character also arrive as a TextInput event
+                    Some(key) if text_input.is_active() && key.types_character() => {}
+                    Some(key) => pending_inputs.push_back(PlayerInput::KeyDown(key)),
+                    None => {}
                 },
                 _ => {}
             }
         }
+        let player_input = pending_inputs.pop_front();
         performance_tracker.end_unit_of_work("get_input").unwrap();
 
         performance_tracker.measure_unit_of_work("game_tick", || {
@@ -92,11 +302,15 @@ pub fn start() {
         });
 
         performance_tracker.measure_unit_of_work("render", || {
-            game_state.render(&mut canvas);
+            game_state.render(&mut renderer);
         });
 
         performance_tracker.measure_unit_of_work("present_canvas", || {
-            canvas.present();
+            renderer.present();
+        });
+
+        performance_tracker.measure_unit_of_work("capture", || {
+            match_capture.capture_frame(&game_state, player_input);
         });
 
         // Output frame performance metrics
@@ -104,11 +318,208 @@ pub fn start() {
         println!("{}", performance_tracker);
 
         // Cap FPS to Frame Rate
-        let remaining_frame_duration = if elapsed > frame_duration as u128 {
-            0
-        } else {
-            frame_duration - u32::try_from(elapsed).unwrap_or(0)
-        };
-        std::thread::sleep(Duration::new(0, remaining_frame_duration));
+        cap_frame_rate(elapsed, frame_duration);
+    }
+
+    match_capture.finish();
+}
+
+pub fn start_terminal(cli_args: &CliArgs) {
+    // Warnings would draw over the arena
+    set_warnings_enabled(false);
+    let mut renderer = TerminalRenderer::new(LOGICAL_WIDTH, LOGICAL_HEIGHT)
+        .expect("Failed to set up terminal renderer");
+    let mut terminal_input = TerminalInput::new();
+
+    let mut game_state = Game::init(cli_args.map_size.width, cli_args.map_size.height);
+    game_state.set_viewport(LOGICAL_WIDTH, LOGICAL_HEIGHT);
+    let mut match_capture = MatchCapture::new(cli_args, &game_state);
+
+    let fps = 60;
+    let frame_duration = 1_000_000_000u32 / fps;
+    // Queued like in the SDL loop
+    let mut pending_inputs: VecDeque<PlayerInput> = VecDeque::new();
+
+    'running: loop {
+        let mut performance_tracker = PerformanceTracker::new();
+
+        // Check for events
+        terminal_input.set_text_input(game_state.wants_text_input());
+        for event in terminal_input.poll_events().unwrap() {
+            match event {
+                TerminalEvent::Quit => break 'running,
+                TerminalEvent::Input(input) => pending_inputs.push_back(input),
+            }
+        }
+        let player_input = pending_inputs.pop_front();
+
+        game_state.tick(player_input);
+
+        renderer.clear(Color::BLACK);
+        game_state.render(&mut renderer);
+        renderer.present();
+        if renderer.write_error().is_some() {
+            break 'running;
+        }
+
+        match_capture.capture_frame(&game_state, player_input);
+
+        // Performance metrics are not printed, as they would draw over the arena
+        let elapsed = performance_tracker.end().unwrap().as_nanos();
+        cap_frame_rate(elapsed, frame_duration);
+    }
+
+    // Restore the terminal before reporting anything
+    let write_error = renderer.write_error().map(|err| err.to_string());
+    drop(renderer);
+    set_warnings_enabled(true);
+    if let Some(err) = write_error {
+        warn(format!(
+            "Failed to write frame to terminal, stopped: {}",
+            err
+        ));
     }
+    match_capture.finish();
+}
+
+pub fn record_replay(replay_path: &Path, output_path: &Path, recording_options: RecordingOptions) {
+    let replay = Replay::load(replay_path).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    });
+
+    let mut game_state = Game::init_with_seed(replay.map_width, replay.map_height, replay.seed);
+    game_state.set_viewport(replay.viewport_width, replay.viewport_height);
+    let mut recorder = FrameRecorder::new(
+        replay.viewport_width,
+        replay.viewport_height,
+        output_path,
+        recording_options,
+    )
+    .expect("Failed to start recording");
+
+    for player_input in &replay.frames {
+        game_state.tick(*player_input);
+
+        recorder.clear(Color::BLACK);
+        game_state.render(&mut recorder);
+        recorder.present();
+        if recorder.is_stopped() {
+            break;
+        }
+    }
+
+    println!(
+        "Recorded {} of {} frames to {}",
+        recorder.frames_captured(),
+        replay.frames.len(),
+        output_path.display()
+    );
+}
+
+/// Optional recording of a live match: its frames (`--record`) and/or its inputs (`--save-replay`)
+struct MatchCapture {
+    recorder: Option<FrameRecorder>,
+    replay: Option<(Replay, PathBuf)>,
+}
+
+impl MatchCapture {
+    fn new(cli_args: &CliArgs, game_state: &Game) -> Self {
+        let viewport = game_state.camera().viewport();
+        let recorder = cli_args.record.as_ref().map(|path| {
+            FrameRecorder::new(
+                viewport.width,
+                viewport.height,
+                path,
+                cli_args.recording_options,
+            )
+            .expect("Failed to start recording")
+        });
+        let replay = cli_args.save_replay.as_ref().map(|path| {
+            (
+                Replay::new(game_state.map_dimensions(), viewport, game_state.seed()),
+                path.clone(),
+            )
+        });
+
+        Self { recorder, replay }
+    }
+
+    fn capture_frame(&mut self, game_state: &Game, player_input: Option<PlayerInput>) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.clear(Color::BLACK);
+            game_state.render(recorder);
+            recorder.present();
+            // No need to keep rendering frames that cannot be written
+            if recorder.is_stopped() {
+                self.recorder = None;
+            }
+        }
+        if let Some((replay, _)) = &mut self.replay {
+            replay.record_frame(player_input);
+        }
+    }
+
+    fn finish(self) {
+        if let Some((replay, path)) = self.replay {
+            match replay.save(&path) {
+                Ok(()) => println!("Saved replay to {}", path.display()),
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+}
+
+fn sdl_key(keycode: Keycode) -> Option<Key> {
+    let key = match keycode {
+        Keycode::W => Key::W,
+        Keycode::A => Key::A,
+        Keycode::S => Key::S,
+        Keycode::D => Key::D,
+        Keycode::I => Key::I,
+        Keycode::J => Key::J,
+        Keycode::K => Key::K,
+        Keycode::L => Key::L,
+        Keycode::Num1 => Key::Num1,
+        Keycode::Num2 => Key::Num2,
+        Keycode::Num3 => Key::Num3,
+        Keycode::Num4 => Key::Num4,
+        Keycode::Num5 => Key::Num5,
+        Keycode::Space => Key::SpaceBar,
+        Keycode::P => Key::Pause,
+        Keycode::Up => Key::Up,
+        Keycode::Down => Key::Down,
+        Keycode::Left => Key::Left,
+        Keycode::Right => Key::Right,
+        Keycode::Return => Key::Enter,
+        Keycode::Backspace => Key::Backspace,
+        _ => return None,
+    };
+
+    Some(key)
+}
+
+/// Largest whole multiple of the logical size that fits comfortably on the primary display
+fn default_window_size(video_subsystem: &sdl2::VideoSubsystem) -> Dimensions {
+    let scale = video_subsystem
+        .desktop_display_mode(0)
+        .map(|mode| {
+            // Leave room for window decorations and task bars
+            let usable_width = mode.w as u32 * 9 / 10;
+            let usable_height = mode.h as u32 * 9 / 10;
+            u32::min(usable_width / LOGICAL_WIDTH, usable_height / LOGICAL_HEIGHT)
+        })
+        .unwrap_or(1)
+        .max(1);
+
+    Dimensions::new(LOGICAL_WIDTH * scale, LOGICAL_HEIGHT * scale)
+}
+
+fn cap_frame_rate(elapsed: u128, frame_duration: u32) {
+    let remaining_frame_duration = if elapsed > frame_duration as u128 {
+        0
+    } else {
+        frame_duration - u32::try_from(elapsed).unwrap_or(0)
+    };
+    std::thread::sleep(Duration::new(0, remaining_frame_duration));
 }
@@ -0,0 +1,7 @@
+mod particle;
+mod particle_emitter;
+mod particle_system;
+
+pub use particle::*;
+pub use particle_emitter::*;
+pub use particle_system::*;
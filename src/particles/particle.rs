@@ -0,0 +1,75 @@
+use crate::structs::{Color, Point};
+
+///
+/// Particle definition
+///
+/// A purely visual point that moves, falls and fades. Particles never take part in collision.
+#[derive(Debug, Copy, Clone)]
+pub struct Particle {
+    x: f32,
+    y: f32,
+    velocity_x: f32,
+    velocity_y: f32,
+    // Added to the vertical velocity every tick
+    gravity: f32,
+    age: u32,
+    lifetime: u32,
+    start_color: Color,
+    end_color: Color,
+}
+
+///
+/// Particle implementation
+///
+impl Particle {
+    pub fn new(
+        origin: Point,
+        velocity: (f32, f32),
+        gravity: f32,
+        lifetime: u32,
+        start_color: Color,
+        end_color: Color,
+    ) -> Self {
+        Self {
+            x: origin.x as f32,
+            y: origin.y as f32,
+            velocity_x: velocity.0,
+            velocity_y: velocity.1,
+            gravity,
+            age: 0,
+            lifetime,
+            start_color,
+            end_color,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.velocity_y += self.gravity;
+        self.x += self.velocity_x;
+        self.y += self.velocity_y;
+        self.age += 1;
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.age < self.lifetime
+    }
+
+    pub fn position(&self) -> Point {
+        Point::new(self.x.round() as i32, self.y.round() as i32)
+    }
+
+    /// Color fades linearly (including alpha) from the start to the end color over the lifetime
+    pub fn color(&self) -> Color {
+        let progress = self.age as f32 / self.lifetime.max(1) as f32;
+        let lerp = |start: u8, end: u8| -> u8 {
+            (start as f32 + (end as f32 - start as f32) * progress).round() as u8
+        };
+
+        Color::RGBA(
+            lerp(self.start_color.r, self.end_color.r),
+            lerp(self.start_color.g, self.end_color.g),
+            lerp(self.start_color.b, self.end_color.b),
+            lerp(self.start_color.a, self.end_color.a),
+        )
+    }
+}
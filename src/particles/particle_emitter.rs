@@ -0,0 +1,84 @@
+use crate::particles::Particle;
+use crate::structs::{Color, Point};
+use rand::Rng;
+use std::f32::consts::TAU;
+
+///
+/// ParticleEmitter definition
+///
+/// Describes a burst of particles: how many, how fast, in which directions and how they fade.
+#[derive(Debug, Copy, Clone)]
+pub struct ParticleEmitter {
+    pub count: u32,
+    // Pixels per tick
+    pub min_speed: f32,
+    pub max_speed: f32,
+    // Emission angle in radians (0 points right, clockwise since y grows downwards) and the
+    // total spread around it. A spread of TAU emits in every direction
+    pub angle: f32,
+    pub spread: f32,
+    // Ticks
+    pub min_lifetime: u32,
+    pub max_lifetime: u32,
+    pub gravity: f32,
+    pub start_color: Color,
+    pub end_color: Color,
+}
+
+///
+/// ParticleEmitter implementation
+///
+impl ParticleEmitter {
+    /// Sparks for a projectile hitting a wall
+    pub fn impact() -> Self {
+        Self {
+            count: 12,
+            min_speed: 0.5,
+            max_speed: 2.5,
+            angle: 0.0,
+            spread: TAU,
+            min_lifetime: 8,
+            max_lifetime: 20,
+            gravity: 0.0,
+            start_color: Color::RGBA(255, 230, 120, 255),
+            end_color: Color::RGBA(255, 60, 0, 0),
+        }
+    }
+
+    /// Glitter for a collected coin
+    pub fn coin_pickup() -> Self {
+        Self {
+            count: 24,
+            min_speed: 0.5,
+            max_speed: 2.0,
+            angle: 0.0,
+            spread: TAU,
+            min_lifetime: 20,
+            max_lifetime: 40,
+            gravity: 0.05,
+            start_color: Color::RGBA(120, 255, 120, 255),
+            end_color: Color::RGBA(255, 255, 255, 0),
+        }
+    }
+
+    pub fn emit<R: Rng>(&self, origin: Point, rng: &mut R) -> Vec<Particle> {
+        (0..self.count)
+            .map(|_| {
+                let angle = self.angle + self.spread * (rng.random::<f32>() - 0.5);
+                let speed =
+                    self.min_speed + (self.max_speed - self.min_speed) * rng.random::<f32>();
+                let lifetime =
+                    rng.random_range(self.min_lifetime..=self.max_lifetime.max(self.min_lifetime));
+
+                Particle::new(
+                    origin,
+                    (angle.cos() * speed, angle.sin() * speed),
+                    self.gravity,
+                    lifetime,
+                    self.start_color,
+                    self.end_color,
+                )
+            })
+            .collect()
+    }
+}
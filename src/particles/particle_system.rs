@@ -0,0 +1,61 @@
+use crate::particles::{Particle, ParticleEmitter};
+use crate::structs::{Camera, Point};
+use crate::traits::Renderer;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// Oldest particles are dropped beyond this, so effects can never slow the game down
+const DEFAULT_MAX_PARTICLES: usize = 4096;
+
+///
+/// ParticleSystem definition
+///
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    max_particles: usize,
+    // Kept apart from the game's RNG so effects never change gameplay, while replays still
+    // produce identical frames
+    rng: StdRng,
+}
+
+///
+/// ParticleSystem implementation
+///
+impl ParticleSystem {
+    pub fn new(seed: u64) -> Self {
+        Self::with_capacity(seed, DEFAULT_MAX_PARTICLES)
+    }
+
+    pub fn with_capacity(seed: u64, max_particles: usize) -> Self {
+        Self {
+            particles: Vec::with_capacity(max_particles),
+            max_particles,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn emit(&mut self, emitter: &ParticleEmitter, origin: Point) {
+        self.particles.extend(emitter.emit(origin, &mut self.rng));
+
+        if self.particles.len() > self.max_particles {
+            let excess = self.particles.len() - self.max_particles;
+            self.particles.drain(..excess);
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.particles
+            .iter_mut()
+            .for_each(|particle| particle.tick());
+        self.particles.retain(|particle| particle.is_alive());
+    }
+
+    pub fn render(&self, renderer: &mut dyn Renderer, camera: &Camera) {
+        for particle in &self.particles {
+            renderer.draw_point(
+                camera.world_to_screen(particle.position()),
+                particle.color(),
+            );
+        }
+    }
+}
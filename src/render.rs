@@ -0,0 +1,11 @@
+mod bitmap_font;
+mod frame_buffer;
+mod frame_recorder;
+mod sdl_renderer;
+mod terminal_renderer;
+
+pub use bitmap_font::*;
+pub use frame_buffer::*;
+pub use frame_recorder::*;
+pub use sdl_renderer::*;
+pub use terminal_renderer::*;
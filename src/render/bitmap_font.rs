@@ -0,0 +1,235 @@
+use crate::structs::{Color, Dimensions, GlyphMetadata, Pixel, Point, Sprite, SpriteError};
+use crate::traits::Renderer;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub const DEFAULT_FONT_NAME: &str = "font";
+
+///
+/// TextAlign definition
+///
+/// Which part of each line is placed at the position text is drawn at
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+///
+/// TextStyle definition
+///
+#[derive(Debug, Copy, Clone)]
+pub struct TextStyle {
+    // Multiplies the glyph colors, so white glyphs are drawn in exactly this color
+    pub color: Color,
+    pub align: TextAlign,
+    // Whole-pixel scale factor, so glyphs stay crisp
+    pub scale: u32,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            align: TextAlign::Left,
+            scale: 1,
+        }
+    }
+}
+
+///
+/// BitmapFont definition
+///
+/// Text drawn from a grid of glyphs in a sprite image. The sprite metadata describes the grid in
+/// its `glyphs` section.
+#[derive(Debug)]
+pub struct BitmapFont {
+    // Name of the font sprite, which glyph sprites are named after
+    name: String,
+    glyph_dimensions: Dimensions,
+    spacing: u32,
+    line_spacing: u32,
+    // Pixels of each glyph relative to the top-left corner of its cell
+    glyphs: HashMap<char, Vec<(Point, Color)>>,
+    // Glyphs already tinted and scaled into sprites, keyed by character, color and scale, so
+    // renderers can cache them like any other sprite
+    glyph_sprites: RefCell<HashMap<(char, Color, u32), Sprite>>,
+}
+
+///
+/// BitmapFont implementation
+///
+impl BitmapFont {
+    /// Loads a font with the same asset lookup as sprites (asset root, then embedded files).
+    pub fn new_from_file(file_name: &str) -> Result<Self, SpriteError> {
+        let (sprite, metadata) = Sprite::new_from_file_with_metadata(file_name)?;
+        let glyphs = metadata
+            .glyphs
+            .ok_or_else(|| SpriteError::MissingGlyphs(PathBuf::from(file_name)))?;
+
+        Ok(BitmapFont::from_sprite(&sprite, metadata.origin, &glyphs))
+    }
+
+    /// Slices a sprite into glyphs. `origin` is the sprite's origin in image space, so glyph
+    /// cells can be located in the sprite's pixels.
+    pub fn from_sprite(sprite: &Sprite, origin: Point, metadata: &GlyphMetadata) -> Self {
+        let glyphs = metadata
+            .characters
+            .chars()
+            .enumerate()
+            .map(|(index, character)| {
+                let (cell_x, cell_y) = metadata.cell_origin(index as u32);
+                let pixels = (0..metadata.glyph_height)
+                    .flat_map(|y| (0..metadata.glyph_width).map(move |x| (x, y)))
+                    .filter_map(|(x, y)| {
+                        let sprite_point = Point::new(
+                            (cell_x + x) as i32 - origin.x,
+                            (cell_y + y) as i32 - origin.y,
+                        );
+                        sprite
+                            .pixels()
+                            .get(sprite_point)
+                            .map(|color| (Point::new(x as i32, y as i32), color))
+                    })
+                    .collect();
+
+                (character, pixels)
+            })
+            .collect();
+
+        Self {
+            name: sprite.name().to_owned(),
+            glyph_dimensions: metadata.glyph_dimensions(),
+            spacing: metadata.spacing,
+            line_spacing: metadata.line_spacing,
+            glyphs,
+            glyph_sprites: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Size of `text` when drawn at `scale`. Lines are separated by `\n`.
+    pub fn measure(&self, text: &str, scale: u32) -> Dimensions {
+        let width = text
+            .lines()
+            .map(|line| self.line_width(line))
+            .max()
+            .unwrap_or(0);
+        let line_count = text.lines().count() as u32;
+        let height = match line_count {
+            0 => 0,
+            _ => line_count * self.line_height() - self.line_spacing,
+        };
+
+        Dimensions::new(width * scale, height * scale)
+    }
+
+    /// Draws `text` with the top of its first line at `position.y`. Horizontally, `position.x`
+    /// is the left edge, center or right edge of every line depending on the alignment.
+    pub fn draw_text(
+        &self,
+        renderer: &mut dyn Renderer,
+        text: &str,
+        position: Point,
+        style: &TextStyle,
+    ) {
+        let scale = style.scale.max(1);
+        let mut glyph_sprites = self.glyph_sprites.borrow_mut();
+
+        for (line_index, line) in text.lines().enumerate() {
+            let line_width = (self.line_width(line) * scale) as i32;
+            let mut x = match style.align {
+                TextAlign::Left => position.x,
+                TextAlign::Center => position.x - line_width / 2,
+                TextAlign::Right => position.x - line_width,
+            };
+            let y = position.y + (line_index as u32 * self.line_height() * scale) as i32;
+
+            for character in line.chars() {
+                if let Some(character) = self.glyph_character(character) {
+                    let sprite = glyph_sprites
+                        .entry((character, style.color, scale))
+                        .or_insert_with(|| self.glyph_sprite(character, style.color, scale));
+                    renderer.draw_sprite(sprite, Point::new(x, y), 1.0);
+                }
+                x += (self.advance() * scale) as i32;
+            }
+        }
+    }
+
+    /// Glyph drawn for `character`. Falls back to the other case for fonts that only contain
+    /// one, and to `?` for characters the font does not have.
+    fn glyph_character(&self, character: char) -> Option<char> {
+        let fallback = match character.is_whitespace() {
+            true => None,
+            false => Some('?'),
+        };
+
+        [
+            Some(character),
+            Some(character.to_ascii_uppercase()),
+            Some(character.to_ascii_lowercase()),
+            fallback,
+        ]
+        .into_iter()
+        .flatten()
+        .find(|candidate| self.glyphs.contains_key(candidate))
+    }
+
+    /// Sprite of a glyph in `color`, with every pixel grown to `scale` by `scale` pixels. Its
+    /// origin is the top-left corner of the glyph cell.
+    fn glyph_sprite(&self, character: char, color: Color, scale: u32) -> Sprite {
+        let scale = scale as i32;
+        let pixels = self.glyphs[&character]
+            .iter()
+            .flat_map(|(point, glyph_color)| {
+                let pixel = Pixel {
+                    color: tint(*glyph_color, color),
+                };
+                (0..scale * scale).map(move |index| {
+                    (
+                        Point::new(
+                            point.x * scale + index % scale,
+                            point.y * scale + index / scale,
+                        ),
+                        pixel,
+                    )
+                })
+            });
+
+        Sprite::new(
+            &format!("{}:{}:{:?}:{}", self.name, character, color, scale),
+            pixels,
+        )
+    }
+
+    fn advance(&self) -> u32 {
+        self.glyph_dimensions.width + self.spacing
+    }
+
+    fn line_height(&self) -> u32 {
+        self.glyph_dimensions.height + self.line_spacing
+    }
+
+    fn line_width(&self, line: &str) -> u32 {
+        match line.chars().count() as u32 {
+            0 => 0,
+            count => count * self.advance() - self.spacing,
+        }
+    }
+}
+
+fn tint(color: Color, tint: Color) -> Color {
+    let multiply = |channel: u8, tint_channel: u8| -> u8 {
+        ((channel as u32 * tint_channel as u32 + 127) / 255) as u8
+    };
+
+    Color::RGBA(
+        multiply(color.r, tint.r),
+        multiply(color.g, tint.g),
+        multiply(color.b, tint.b),
+        multiply(color.a, tint.a),
+    )
+}
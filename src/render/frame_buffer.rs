@@ -0,0 +1,81 @@
+use crate::structs::{Color, Dimensions, Point, Sprite};
+use crate::traits::Renderer;
+
+///
+/// FrameBuffer definition
+///
+/// Software renderer that draws into an in-memory grid of colors
+pub struct FrameBuffer {
+    dimensions: Dimensions,
+    // Row-major colors, `dimensions.width * dimensions.height` long
+    pixels: Vec<Color>,
+}
+
+///
+/// FrameBuffer implementation
+///
+impl FrameBuffer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            dimensions: Dimensions::new(width, height),
+            pixels: vec![Color::BLACK; width as usize * height as usize],
+        }
+    }
+
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> Color {
+        self.pixels[y as usize * self.dimensions.width as usize + x as usize]
+    }
+
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    fn blend_pixel(&mut self, point: Point, color: Color, opacity: f32) {
+        if point.x < 0
+            || point.y < 0
+            || point.x as u32 >= self.dimensions.width
+            || point.y as u32 >= self.dimensions.height
+        {
+            return;
+        }
+
+        let index = point.y as usize * self.dimensions.width as usize + point.x as usize;
+        self.pixels[index] = color.blend_over(self.pixels[index], opacity);
+    }
+}
+
+impl Renderer for FrameBuffer {
+    fn clear(&mut self, color: Color) {
+        self.pixels.fill(color);
+    }
+
+    fn draw_sprite(&mut self, sprite: &Sprite, origin: Point, opacity: f32) {
+        for (point, color) in sprite.pixels().iter() {
+            self.blend_pixel(
+                Point::new(origin.x + point.x, origin.y + point.y),
+                color,
+                opacity,
+            );
+        }
+    }
+
+    fn draw_point(&mut self, point: Point, color: Color) {
+        self.blend_pixel(point, color, 1.0);
+    }
+
+    fn fill_rect(&mut self, origin: Point, dimensions: Dimensions, color: Color) {
+        for y in 0..dimensions.height as i32 {
+            for x in 0..dimensions.width as i32 {
+                self.blend_pixel(Point::new(origin.x + x, origin.y + y), color, 1.0);
+            }
+        }
+    }
+
+    fn present(&mut self) {
+        // Nothing to present, the pixels are read directly
+    }
+}
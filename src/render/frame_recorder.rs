@@ -0,0 +1,170 @@
+use crate::render::FrameBuffer;
+use crate::structs::{Color, Dimensions, Point, Sprite};
+use crate::traits::Renderer;
+use crate::util::warn;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::imageops::FilterType;
+use image::{Delay, Frame, ImageResult, RgbaImage};
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+///
+/// RecordingOptions definition
+///
+#[derive(Debug, Copy, Clone)]
+pub struct RecordingOptions {
+    // Number of frames to skip after each captured frame
+    pub frame_skip: u32,
+    // Factor applied to the frame size before it is written
+    pub scale: f32,
+    // Frame rate of the game, used for GIF frame delays
+    pub frame_rate: u32,
+}
+
+impl Default for RecordingOptions {
+    fn default() -> Self {
+        Self {
+            frame_skip: 0,
+            scale: 1.0,
+            frame_rate: 60,
+        }
+    }
+}
+
+///
+/// FrameRecorder definition
+///
+/// Renderer that captures presented frames to an animated GIF (when the output path ends in
+/// `.gif`) or to a directory of numbered PNGs. The GIF is finalized when the recorder is dropped.
+pub struct FrameRecorder {
+    frame_buffer: FrameBuffer,
+    options: RecordingOptions,
+    output: RecordingOutput,
+    frames_presented: u64,
+    frames_captured: u64,
+    // Set when a frame fails to be written; nothing is captured after that
+    stopped: bool,
+}
+
+enum RecordingOutput {
+    Gif(Box<GifEncoder<BufWriter<File>>>),
+    PngSequence(PathBuf),
+}
+
+///
+/// FrameRecorder implementation
+///
+impl FrameRecorder {
+    pub fn new(
+        width: u32,
+        height: u32,
+        output_path: &Path,
+        options: RecordingOptions,
+    ) -> ImageResult<Self> {
+        let is_gif = output_path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("gif"))
+            .unwrap_or(false);
+
+        let output = if is_gif {
+            let mut encoder = GifEncoder::new(BufWriter::new(File::create(output_path)?));
+            encoder.set_repeat(Repeat::Infinite)?;
+            RecordingOutput::Gif(Box::new(encoder))
+        } else {
+            fs::create_dir_all(output_path)?;
+            RecordingOutput::PngSequence(output_path.to_path_buf())
+        };
+
+        Ok(Self {
+            frame_buffer: FrameBuffer::new(width, height),
+            options,
+            output,
+            frames_presented: 0,
+            frames_captured: 0,
+            stopped: false,
+        })
+    }
+
+    pub fn frames_captured(&self) -> u64 {
+        self.frames_captured
+    }
+
+    /// Whether recording stopped early because a frame could not be written
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    fn capture(&mut self) -> ImageResult<()> {
+        let image = self.scaled_frame();
+
+        match &mut self.output {
+            RecordingOutput::Gif(encoder) => {
+                let delay_ms = 1000 * (self.options.frame_skip + 1);
+                let delay = Delay::from_numer_denom_ms(delay_ms, self.options.frame_rate.max(1));
+                encoder.encode_frame(Frame::from_parts(image, 0, 0, delay))?;
+            }
+            RecordingOutput::PngSequence(dir) => {
+                image.save(dir.join(format!("frame_{:05}.png", self.frames_captured)))?;
+            }
+        }
+
+        self.frames_captured += 1;
+        Ok(())
+    }
+
+    fn scaled_frame(&self) -> RgbaImage {
+        let dimensions = self.frame_buffer.dimensions();
+        let rgba: Vec<u8> = self
+            .frame_buffer
+            .pixels()
+            .iter()
+            .flat_map(|color| [color.r, color.g, color.b, color.a])
+            .collect();
+        let image = RgbaImage::from_raw(dimensions.width, dimensions.height, rgba)
+            .expect("Frame buffer size does not match its dimensions");
+
+        if self.options.scale == 1.0 {
+            return image;
+        }
+
+        // Nearest neighbour keeps pixel art crisp
+        let width = ((dimensions.width as f32 * self.options.scale) as u32).max(1);
+        let height = ((dimensions.height as f32 * self.options.scale) as u32).max(1);
+        image::imageops::resize(&image, width, height, FilterType::Nearest)
+    }
+}
+
+impl Renderer for FrameRecorder {
+    fn clear(&mut self, color: Color) {
+        self.frame_buffer.clear(color);
+    }
+
+    fn draw_sprite(&mut self, sprite: &Sprite, origin: Point, opacity: f32) {
+        self.frame_buffer.draw_sprite(sprite, origin, opacity);
+    }
+
+    fn draw_point(&mut self, point: Point, color: Color) {
+        self.frame_buffer.draw_point(point, color);
+    }
+
+    fn fill_rect(&mut self, origin: Point, dimensions: Dimensions, color: Color) {
+        self.frame_buffer.fill_rect(origin, dimensions, color);
+    }
+
+    fn present(&mut self) {
+        let captures_frame = self
+            .frames_presented
+            .is_multiple_of(self.options.frame_skip as u64 + 1);
+        if !self.stopped && captures_frame {
+            if let Err(err) = self.capture() {
+                warn(format!(
+                    "Failed to capture frame, stopped recording: {}",
+                    err
+                ));
+                self.stopped = true;
+            }
+        }
+        self.frames_presented += 1;
+    }
+}
@@ -0,0 +1,197 @@
+use crate::structs::{Color, Dimensions, Point, Sprite};
+use crate::traits::Renderer;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::rect::Rect;
+use sdl2::render::{BlendMode, Texture, TextureCreator, WindowCanvas};
+use sdl2::video::{FullscreenType, WindowContext};
+use std::collections::HashMap;
+
+///
+/// ScalingMode definition
+///
+/// How the logical canvas is fitted into the window. Both modes keep the aspect ratio and
+/// letterbox the remaining space.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScalingMode {
+    // Only whole multiples of the logical size, so every world pixel is the same size on screen
+    Integer,
+    // The largest scale that fits, which may be fractional
+    Letterbox,
+}
+
+///
+/// SdlRenderer definition
+///
+pub struct SdlRenderer<'a> {
+    canvas: WindowCanvas,
+    texture_creator: &'a TextureCreator<WindowContext>,
+    // Each sprite is uploaded once per rotation, keyed by `Sprite::id` and indexed by
+    // `Rotation::index`
+    sprite_textures: HashMap<u32, [Option<SpriteTexture<'a>>; 4]>,
+}
+
+struct SpriteTexture<'a> {
+    texture: Texture<'a>,
+    // Top-left corner of the texture relative to the sprite origin
+    offset: Point,
+    dimensions: Dimensions,
+}
+
+///
+/// SdlRenderer implementation
+///
+impl<'a> SdlRenderer<'a> {
+    pub fn new(
+        mut canvas: WindowCanvas,
+        texture_creator: &'a TextureCreator<WindowContext>,
+    ) -> Self {
+        // Nearest neighbour scaling keeps pixel art crisp. Must be set before textures are created
+        sdl2::hint::set("SDL_RENDER_SCALE_QUALITY", "nearest");
+
+        canvas.set_blend_mode(BlendMode::Blend);
+
+        Self {
+            canvas,
+            texture_creator,
+            sprite_textures: HashMap::new(),
+        }
+    }
+
+    /// Renders at `width`x`height` regardless of the window size, scaling the result to fit.
+    pub fn set_logical_size(&mut self, width: u32, height: u32, scaling_mode: ScalingMode) {
+        self.canvas
+            .set_logical_size(width, height)
+            .expect("Failed to set logical size");
+        self.canvas
+            .set_integer_scale(scaling_mode == ScalingMode::Integer)
+            .expect("Failed to set scaling mode");
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.canvas.window().fullscreen_state() != FullscreenType::Off
+    }
+
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        let fullscreen_type = match fullscreen {
+            true => FullscreenType::Desktop,
+            false => FullscreenType::Off,
+        };
+
+        if let Err(err) = self.canvas.window_mut().set_fullscreen(fullscreen_type) {
+            eprintln!("Failed to change fullscreen mode: {}", err);
+        }
+    }
+
+    pub fn toggle_fullscreen(&mut self) {
+        self.set_fullscreen(!self.is_fullscreen());
+    }
+
+    fn create_sprite_texture(&self, sprite: &Sprite) -> SpriteTexture<'a> {
+        let (offset, dimensions) = sprite.bounds();
+        // SDL cannot create zero-sized textures
+        let (width, height) = (dimensions.width.max(1), dimensions.height.max(1));
+
+        // The sprite's buffer already covers its bounds and is transparent where it has no
+        // pixel, so it can be uploaded as tightly packed RGBA bytes
+        let pitch = width as usize * 4;
+        let mut rgba: Vec<u8> = sprite
+            .pixels()
+            .colors()
+            .iter()
+            .flat_map(|color| [color.r, color.g, color.b, color.a])
+            .collect();
+        rgba.resize(pitch * height as usize, 0);
+
+        let mut texture = self
+            .texture_creator
+            .create_texture_static(PixelFormatEnum::RGBA32, width, height)
+            .expect("Failed to create sprite texture");
+        texture
+            .update(None, &rgba, pitch)
+            .expect("Failed to upload sprite texture");
+        texture.set_blend_mode(BlendMode::Blend);
+
+        SpriteTexture {
+            texture,
+            offset,
+            dimensions,
+        }
+    }
+}
+
+impl Renderer for SdlRenderer<'_> {
+    fn clear(&mut self, color: Color) {
+        self.canvas.set_draw_color(sdl2::pixels::Color::RGBA(
+            color.r, color.g, color.b, color.a,
+        ));
+        self.canvas.clear();
+    }
+
+    fn draw_sprite(&mut self, sprite: &Sprite, origin: Point, opacity: f32) {
+        let rotation = sprite.rotation().index();
+        let is_cached = self
+            .sprite_textures
+            .get(&sprite.id())
+            .is_some_and(|textures| textures[rotation].is_some());
+        if !is_cached {
+            let sprite_texture = self.create_sprite_texture(sprite);
+            self.sprite_textures
+                .entry(sprite.id())
+                .or_insert_with(|| [None, None, None, None])[rotation] = Some(sprite_texture);
+        }
+
+        let sprite_texture = self
+            .sprite_textures
+            .get_mut(&sprite.id())
+            .and_then(|textures| textures[rotation].as_mut())
+            .unwrap();
+        if sprite_texture.dimensions.width == 0 || sprite_texture.dimensions.height == 0 {
+            return;
+        }
+
+        let destination = Rect::new(
+            origin.x + sprite_texture.offset.x,
+            origin.y + sprite_texture.offset.y,
+            sprite_texture.dimensions.width,
+            sprite_texture.dimensions.height,
+        );
+        // Blending with the texture's per-pixel alpha is enabled when the texture is created
+        let alpha_mod = (opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+        sprite_texture.texture.set_alpha_mod(alpha_mod);
+
+        self.canvas
+            .copy(&sprite_texture.texture, None, destination)
+            .unwrap();
+    }
+
+    fn draw_point(&mut self, point: Point, color: Color) {
+        self.canvas.set_draw_color(sdl2::pixels::Color::RGBA(
+            color.r, color.g, color.b, color.a,
+        ));
+        self.canvas
+            .draw_point(sdl2::rect::Point::new(point.x, point.y))
+            .unwrap();
+    }
+
+    fn fill_rect(&mut self, origin: Point, dimensions: Dimensions, color: Color) {
+        if dimensions.width == 0 || dimensions.height == 0 {
+            return;
+        }
+
+        self.canvas.set_draw_color(sdl2::pixels::Color::RGBA(
+            color.r, color.g, color.b, color.a,
+        ));
+        self.canvas
+            .fill_rect(Rect::new(
+                origin.x,
+                origin.y,
+                dimensions.width,
+                dimensions.height,
+            ))
+            .unwrap();
+    }
+
+    fn present(&mut self) {
+        self.canvas.present();
+    }
+}
@@ -0,0 +1,144 @@
+use crate::render::FrameBuffer;
+use crate::structs::{Color, Dimensions, Point, Sprite};
+use crate::traits::Renderer;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::style::{Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+use std::io::{BufWriter, Stdout, Write};
+
+// Upper half block: the foreground color fills the top half of the cell, the background the bottom
+const HALF_BLOCK: char = '\u{2580}';
+
+///
+/// TerminalRenderer definition
+///
+/// Draws the game into the terminal using half-block characters and 24-bit ANSI colors, so each
+/// terminal cell shows two vertically stacked pixels of the downsampled arena.
+pub struct TerminalRenderer {
+    frame_buffer: FrameBuffer,
+    out: BufWriter<Stdout>,
+    // Set when a frame fails to be written; nothing is drawn after that
+    write_error: Option<std::io::Error>,
+}
+
+///
+/// TerminalRenderer implementation
+///
+impl TerminalRenderer {
+    /// Switches the terminal to raw mode on an alternate screen. The terminal is restored when
+    /// the renderer is dropped.
+    pub fn new(width: u32, height: u32) -> std::io::Result<Self> {
+        let mut out = BufWriter::new(std::io::stdout());
+        enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide)?;
+
+        Ok(Self {
+            frame_buffer: FrameBuffer::new(width, height),
+            out,
+            write_error: None,
+        })
+    }
+
+    /// Why drawing stopped, if a frame could not be written to the terminal
+    pub fn write_error(&self) -> Option<&std::io::Error> {
+        self.write_error.as_ref()
+    }
+
+    fn write_frame(&mut self) -> std::io::Result<()> {
+        let (columns, rows) = crossterm::terminal::size()?;
+        let dimensions = self.frame_buffer.dimensions();
+
+        // Source pixels per terminal half-cell, keeping the arena's aspect ratio
+        let scale = f32::max(
+            dimensions.width as f32 / columns.max(1) as f32,
+            dimensions.height as f32 / (rows.max(1) as f32 * 2.0),
+        );
+
+        let mut current_colors: Option<(Color, Color)> = None;
+        for row in 0..rows {
+            queue!(self.out, MoveTo(0, row))?;
+
+            for column in 0..columns {
+                let top = self.sample(column as u32, row as u32 * 2, scale);
+                let bottom = self.sample(column as u32, row as u32 * 2 + 1, scale);
+
+                if current_colors != Some((top, bottom)) {
+                    queue!(
+                        self.out,
+                        SetForegroundColor(to_terminal_color(top)),
+                        SetBackgroundColor(to_terminal_color(bottom))
+                    )?;
+                    current_colors = Some((top, bottom));
+                }
+                queue!(self.out, Print(HALF_BLOCK))?;
+            }
+        }
+
+        queue!(self.out, ResetColor)?;
+        self.out.flush()
+    }
+
+    /// Color of the half-cell at (x, y). Uses the brightest source pixel it covers, so thin walls
+    /// and small projectiles stay visible when downsampled.
+    fn sample(&self, x: u32, y: u32, scale: f32) -> Color {
+        let dimensions = self.frame_buffer.dimensions();
+        let x_start = (x as f32 * scale) as u32;
+        let y_start = (y as f32 * scale) as u32;
+        if x_start >= dimensions.width || y_start >= dimensions.height {
+            return Color::BLACK;
+        }
+
+        let x_end = (((x + 1) as f32 * scale) as u32).clamp(x_start + 1, dimensions.width);
+        let y_end = (((y + 1) as f32 * scale) as u32).clamp(y_start + 1, dimensions.height);
+
+        (y_start..y_end)
+            .flat_map(|source_y| (x_start..x_end).map(move |source_x| (source_x, source_y)))
+            .map(|(source_x, source_y)| self.frame_buffer.pixel(source_x, source_y))
+            .max_by_key(|color| color.r as u32 + color.g as u32 + color.b as u32)
+            .unwrap_or(Color::BLACK)
+    }
+}
+
+impl Renderer for TerminalRenderer {
+    fn clear(&mut self, color: Color) {
+        self.frame_buffer.clear(color);
+    }
+
+    fn draw_sprite(&mut self, sprite: &Sprite, origin: Point, opacity: f32) {
+        self.frame_buffer.draw_sprite(sprite, origin, opacity);
+    }
+
+    fn draw_point(&mut self, point: Point, color: Color) {
+        self.frame_buffer.draw_point(point, color);
+    }
+
+    fn fill_rect(&mut self, origin: Point, dimensions: Dimensions, color: Color) {
+        self.frame_buffer.fill_rect(origin, dimensions, color);
+    }
+
+    fn present(&mut self) {
+        if self.write_error.is_none() {
+            if let Err(err) = self.write_frame() {
+                self.write_error = Some(err);
+            }
+        }
+    }
+}
+
+impl Drop for TerminalRenderer {
+    fn drop(&mut self) {
+        let _ = execute!(self.out, ResetColor, Show, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+fn to_terminal_color(color: Color) -> crossterm::style::Color {
+    crossterm::style::Color::Rgb {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+    }
+}
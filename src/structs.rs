@@ -1,11 +1,37 @@
+mod bounding_box;
+mod camera;
+mod footprint;
 mod game_object_type;
+mod glyph_metadata;
+mod hitbox;
 mod movement;
+mod object_pool;
 mod pixel;
+mod pixel_buffer;
 mod player_input;
+mod render_layer;
+mod replay;
+mod spatial_grid;
 mod sprite;
+mod sprite_error;
+mod sweep_hit;
+mod terrain_bitmap;
 
+pub use bounding_box::*;
+pub use camera::*;
+pub use footprint::*;
 pub use game_object_type::*;
+pub use glyph_metadata::*;
+pub use hitbox::*;
 pub use movement::*;
+pub use object_pool::*;
 pub use pixel::*;
+pub use pixel_buffer::*;
 pub use player_input::*;
+pub use render_layer::*;
+pub use replay::*;
+pub use spatial_grid::*;
 pub use sprite::*;
+pub use sprite_error::*;
+pub use sweep_hit::*;
+pub use terrain_bitmap::*;
@@ -0,0 +1,68 @@
+use crate::structs::{Dimensions, Point};
+
+///
+/// BoundingBox definition
+///
+/// Axis-aligned box in world space. Both corners are inclusive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+///
+/// BoundingBox implementation
+///
+impl BoundingBox {
+    pub fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_origin(origin: Point, dimensions: Dimensions) -> Self {
+        Self {
+            min: origin,
+            max: Point::new(
+                origin.x + dimensions.width as i32 - 1,
+                origin.y + dimensions.height as i32 - 1,
+            ),
+        }
+    }
+
+    /// Smallest box containing every point, or `None` if there are no points
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Point>) -> Option<Self> {
+        points.into_iter().fold(None, |bounds, point| {
+            Some(match bounds {
+                None => BoundingBox::new(*point, *point),
+                Some(bounds) => bounds.union(&BoundingBox::new(*point, *point)),
+            })
+        })
+    }
+
+    pub fn translate(&self, offset: Point) -> Self {
+        Self {
+            min: Point::new(self.min.x + offset.x, self.min.y + offset.y),
+            max: Point::new(self.max.x + offset.x, self.max.y + offset.y),
+        }
+    }
+
+    pub fn union(&self, other: &BoundingBox) -> Self {
+        Self {
+            min: Point::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Point::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+}
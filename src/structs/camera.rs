@@ -0,0 +1,65 @@
+use crate::structs::{Dimensions, Point};
+
+///
+/// Camera definition
+///
+/// Maps world coordinates onto a viewport (the window or recording), so maps can be larger than
+/// the screen.
+#[derive(Debug, Copy, Clone)]
+pub struct Camera {
+    // World coordinate shown at the top-left of the viewport
+    position: Point,
+    viewport: Dimensions,
+}
+
+///
+/// Camera implementation
+///
+impl Camera {
+    pub fn new(viewport: Dimensions) -> Self {
+        Self {
+            position: Point::default(),
+            viewport,
+        }
+    }
+
+    pub fn position(&self) -> Point {
+        self.position
+    }
+
+    pub fn viewport(&self) -> Dimensions {
+        self.viewport
+    }
+
+    pub fn set_viewport(&mut self, viewport: Dimensions) {
+        self.viewport = viewport;
+    }
+
+    /// Centers the viewport on `target`, clamped so that nothing outside of the map is shown.
+    /// Along an axis where the map is smaller than the viewport, the map is centered instead.
+    pub fn follow(&mut self, target: Point, map_dimensions: Dimensions) {
+        self.position = Point::new(
+            Camera::clamp_axis(target.x, self.viewport.width, map_dimensions.width),
+            Camera::clamp_axis(target.y, self.viewport.height, map_dimensions.height),
+        );
+    }
+
+    pub fn world_to_screen(&self, point: Point) -> Point {
+        Point::new(point.x - self.position.x, point.y - self.position.y)
+    }
+
+    pub fn screen_to_world(&self, point: Point) -> Point {
+        Point::new(point.x + self.position.x, point.y + self.position.y)
+    }
+
+    fn clamp_axis(target: i32, viewport_length: u32, map_length: u32) -> i32 {
+        let viewport_length = viewport_length as i32;
+        let map_length = map_length as i32;
+
+        if map_length <= viewport_length {
+            return -(viewport_length - map_length) / 2;
+        }
+
+        (target - viewport_length / 2).clamp(0, map_length - viewport_length)
+    }
+}
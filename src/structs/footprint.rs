@@ -0,0 +1,58 @@
+use crate::structs::{BoundingBox, Point, Sprite};
+
+///
+/// Footprint definition
+///
+/// A sprite placed in the world at `origin`. Nothing is copied: world-space pixels and collision
+/// points are produced by offsetting the sprite's buffers, so moving an object only changes its
+/// origin.
+#[derive(Debug, Copy, Clone)]
+pub struct Footprint<'a> {
+    sprite: &'a Sprite,
+    origin: Point,
+}
+
+///
+/// Footprint implementation
+///
+impl<'a> Footprint<'a> {
+    pub fn new(sprite: &'a Sprite, origin: Point) -> Self {
+        Self { sprite, origin }
+    }
+
+    pub fn sprite(&self) -> &'a Sprite {
+        self.sprite
+    }
+
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    /// Whether `point` (in world space) is one of the sprite's collision points
+    pub fn is_solid(&self, point: Point) -> bool {
+        self.sprite
+            .collision_points()
+            .contains(self.to_sprite_point(point))
+    }
+
+    /// Every collision point of the sprite in world space
+    pub fn collision_points(self) -> impl Iterator<Item = Point> + 'a {
+        let origin = self.origin;
+        self.sprite
+            .collision_points()
+            .points()
+            .map(move |point| Point::new(origin.x + point.x, origin.y + point.y))
+    }
+
+    /// World-space box around the collision points, or `None` if the sprite has none
+    pub fn collision_bounds(&self) -> Option<BoundingBox> {
+        self.sprite
+            .collision_points()
+            .bounding_box()
+            .map(|bounds| bounds.translate(self.origin))
+    }
+
+    fn to_sprite_point(self, point: Point) -> Point {
+        Point::new(point.x - self.origin.x, point.y - self.origin.y)
+    }
+}
@@ -1,7 +1,7 @@
 ///
 /// GameObjectType definition
 ///
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum GameObjectType {
     Player,
     Coin,
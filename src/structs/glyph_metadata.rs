@@ -0,0 +1,54 @@
+use crate::structs::Dimensions;
+use serde::Deserialize;
+
+/// Layout of a bitmap font image, declared in its metadata under `glyphs`. Glyphs are laid out
+/// in a grid of equally sized cells, left to right and top to bottom, starting at (0, 0).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GlyphMetadata {
+    pub glyph_width: u32,
+    pub glyph_height: u32,
+    // Number of glyph cells per row of the image
+    pub columns: u32,
+    // The character drawn in each cell, in grid order
+    pub characters: String,
+    // Empty pixels between two glyphs on a line
+    #[serde(default = "default_spacing")]
+    pub spacing: u32,
+    // Empty pixels between two lines
+    #[serde(default = "default_spacing")]
+    pub line_spacing: u32,
+}
+
+///
+/// GlyphMetadata implementation
+///
+impl GlyphMetadata {
+    pub fn glyph_dimensions(&self) -> Dimensions {
+        Dimensions::new(self.glyph_width, self.glyph_height)
+    }
+
+    /// Top-left corner of the cell of the `index`th character, in image space
+    pub fn cell_origin(&self, index: u32) -> (u32, u32) {
+        let columns = self.columns.max(1);
+        (
+            (index % columns) * self.glyph_width,
+            (index / columns) * self.glyph_height,
+        )
+    }
+
+    /// Size of the image area covered by all glyph cells
+    pub fn grid_dimensions(&self) -> Dimensions {
+        let count = self.characters.chars().count() as u32;
+        let columns = self.columns.max(1);
+        let rows = count.div_ceil(columns);
+
+        Dimensions::new(
+            columns.min(count) * self.glyph_width,
+            rows * self.glyph_height,
+        )
+    }
+}
+
+fn default_spacing() -> u32 {
+    1
+}
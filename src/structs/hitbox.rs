@@ -0,0 +1,55 @@
+use crate::structs::Point;
+use serde::Deserialize;
+
+/// Collision shape of a sprite, declared in its metadata under `hitbox`. Coordinates are in
+/// image space, the same as the metadata `origin`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HitboxMetadata {
+    // Name of a PNG (without extension) in the same directory. Every non-transparent pixel is solid
+    Mask(String),
+    // Solid shapes that together make up the hitbox
+    Shapes(Vec<HitboxShape>),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HitboxShape {
+    Rect {
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    },
+    Circle {
+        x: i32,
+        y: i32,
+        radius: u32,
+    },
+}
+
+///
+/// HitboxShape implementation
+///
+impl HitboxShape {
+    pub fn points(&self) -> Vec<Point> {
+        match *self {
+            HitboxShape::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => (y..y + height as i32)
+                .flat_map(|py| (x..x + width as i32).map(move |px| Point::new(px, py)))
+                .collect(),
+            HitboxShape::Circle { x, y, radius } => {
+                let radius = radius as i32;
+                (-radius..=radius)
+                    .flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+                    .filter(|(dx, dy)| dx * dx + dy * dy <= radius * radius)
+                    .map(|(dx, dy)| Point::new(x + dx, y + dy))
+                    .collect()
+            }
+        }
+    }
+}
@@ -10,7 +10,7 @@ pub enum Direction {
     Right,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Rotation {
     Up,
     Right,
@@ -35,3 +35,17 @@ impl Default for Rotation {
         Rotation::Up
     }
 }
+
+impl Rotation {
+    pub const ALL: [Rotation; 4] = [
+        Rotation::Up,
+        Rotation::Right,
+        Rotation::Down,
+        Rotation::Left,
+    ];
+
+    /// Position of the rotation in `Rotation::ALL`
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+///
+/// ObjectPool definition
+///
+/// Storage for short-lived objects (e.g. projectiles) keyed by id. Objects are packed densely
+/// for iteration and removed in O(1) by swapping the last one into the gap. Removed objects are
+/// kept, so spawning can reuse them and their allocations instead of building new ones.
+#[derive(Debug, Clone)]
+pub struct ObjectPool<T> {
+    // Live objects, in no particular order
+    objects: Vec<T>,
+    // Key of the object at the same position in `objects`
+    keys: Vec<u32>,
+    // Position of each live key in `objects`
+    positions: HashMap<u32, usize>,
+    // Removed objects waiting to be reused
+    recycled: Vec<T>,
+}
+
+///
+/// ObjectPool implementation
+///
+impl<T> ObjectPool<T> {
+    pub fn new() -> Self {
+        ObjectPool::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            objects: Vec::with_capacity(capacity),
+            keys: Vec::with_capacity(capacity),
+            positions: HashMap::with_capacity(capacity),
+            recycled: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Adds `object` under `key`, replacing (and recycling) any object already stored there
+    pub fn insert(&mut self, key: u32, object: T) {
+        if let Some(position) = self.positions.get(&key) {
+            let replaced = std::mem::replace(&mut self.objects[*position], object);
+            self.recycled.push(replaced);
+            return;
+        }
+
+        self.positions.insert(key, self.objects.len());
+        self.objects.push(object);
+        self.keys.push(key);
+    }
+
+    /// A previously removed object, to be reset and inserted again
+    pub fn recycle(&mut self) -> Option<T> {
+        self.recycled.pop()
+    }
+
+    /// Removes the object stored under `key`. Returns `false` if there is none.
+    pub fn remove(&mut self, key: u32) -> bool {
+        let position = match self.positions.remove(&key) {
+            Some(position) => position,
+            None => return false,
+        };
+
+        let removed = self.objects.swap_remove(position);
+        self.keys.swap_remove(position);
+        // The last object now fills the gap
+        if let Some(moved_key) = self.keys.get(position) {
+            self.positions.insert(*moved_key, position);
+        }
+        self.recycled.push(removed);
+
+        true
+    }
+
+    pub fn contains(&self, key: u32) -> bool {
+        self.positions.contains_key(&key)
+    }
+
+    pub fn get(&self, key: u32) -> Option<&T> {
+        self.positions
+            .get(&key)
+            .map(|position| &self.objects[*position])
+    }
+
+    pub fn get_mut(&mut self, key: u32) -> Option<&mut T> {
+        self.positions
+            .get(&key)
+            .map(|position| &mut self.objects[*position])
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.objects.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.objects.iter_mut()
+    }
+
+    /// Removes every object, keeping them for reuse
+    pub fn clear(&mut self) {
+        self.recycled.append(&mut self.objects);
+        self.keys.clear();
+        self.positions.clear();
+    }
+}
+
+impl<T> Default for ObjectPool<T> {
+    fn default() -> Self {
+        ObjectPool::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_first_moves_last_into_gap() {
+        let mut pool = ObjectPool::new();
+        pool.insert(1, "one");
+        pool.insert(2, "two");
+        pool.insert(3, "three");
+        pool.insert(4, "four");
+
+        assert!(pool.remove(1));
+
+        assert_eq!(pool.len(), 3);
+        assert!(!pool.contains(1));
+        assert_eq!(pool.get(1), None);
+        assert_eq!(pool.get(2), Some(&"two"));
+        assert_eq!(pool.get(3), Some(&"three"));
+        assert_eq!(pool.get(4), Some(&"four"));
+    }
+
+    #[test]
+    fn remove_middle_moves_last_into_gap() {
+        let mut pool = ObjectPool::new();
+        pool.insert(1, "one");
+        pool.insert(2, "two");
+        pool.insert(3, "three");
+        pool.insert(4, "four");
+
+        assert!(pool.remove(2));
+
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.get(2), None);
+        assert_eq!(pool.get(1), Some(&"one"));
+        assert_eq!(pool.get(3), Some(&"three"));
+        assert_eq!(pool.get(4), Some(&"four"));
+
+        // The moved object can still be changed and removed through its key
+        *pool.get_mut(4).unwrap() = "four!";
+        assert_eq!(pool.get(4), Some(&"four!"));
+        assert!(pool.remove(4));
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.get(1), Some(&"one"));
+        assert_eq!(pool.get(3), Some(&"three"));
+    }
+
+    #[test]
+    fn remove_last_moves_nothing() {
+        let mut pool = ObjectPool::new();
+        pool.insert(1, "one");
+        pool.insert(2, "two");
+        pool.insert(3, "three");
+
+        assert!(pool.remove(3));
+
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.get(3), None);
+        assert_eq!(pool.get(1), Some(&"one"));
+        assert_eq!(pool.get(2), Some(&"two"));
+    }
+
+    #[test]
+    fn remove_missing_key() {
+        let mut pool = ObjectPool::new();
+        pool.insert(1, "one");
+
+        assert!(!pool.remove(2));
+        assert!(pool.remove(1));
+        assert!(!pool.remove(1));
+        assert!(pool.is_empty());
+        assert_eq!(pool.recycle(), Some("one"));
+        assert_eq!(pool.recycle(), None);
+    }
+
+    #[test]
+    fn insert_over_existing_key_replaces_and_recycles() {
+        let mut pool = ObjectPool::new();
+        pool.insert(1, "one");
+        pool.insert(2, "two");
+        pool.insert(3, "three");
+
+        pool.insert(2, "replacement");
+
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.get(1), Some(&"one"));
+        assert_eq!(pool.get(2), Some(&"replacement"));
+        assert_eq!(pool.get(3), Some(&"three"));
+        assert_eq!(pool.recycle(), Some("two"));
+        assert_eq!(pool.recycle(), None);
+
+        // The replaced entry still moves correctly when another one is removed
+        assert!(pool.remove(1));
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.get(2), Some(&"replacement"));
+        assert_eq!(pool.get(3), Some(&"three"));
+    }
+
+    #[test]
+    fn clear_recycles_everything() {
+        let mut pool = ObjectPool::new();
+        pool.insert(1, "one");
+        pool.insert(2, "two");
+
+        pool.clear();
+
+        assert!(pool.is_empty());
+        assert!(!pool.contains(1));
+        assert_eq!(pool.get(2), None);
+        assert_eq!(pool.recycle(), Some("two"));
+        assert_eq!(pool.recycle(), Some("one"));
+        assert_eq!(pool.recycle(), None);
+    }
+}
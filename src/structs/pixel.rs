@@ -14,7 +14,7 @@ pub struct Pixel {
     pub color: Color,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -60,9 +60,31 @@ impl Color {
         Self { r, g, b, a }
     }
 
+    /// Source-over blend of this color on top of `destination`. The color's own alpha is
+    /// multiplied by `opacity` (0.0 to 1.0).
+    pub fn blend_over(self, destination: Color, opacity: f32) -> Color {
+        let alpha = (self.a as f32 / 255.0) * opacity.clamp(0.0, 1.0);
+        if alpha >= 1.0 {
+            return self;
+        }
+
+        let blend_channel = |source: u8, destination: u8| -> u8 {
+            (source as f32 * alpha + destination as f32 * (1.0 - alpha)).round() as u8
+        };
+        let destination_alpha = destination.a as f32 / 255.0;
+
+        Color::RGBA(
+            blend_channel(self.r, destination.r),
+            blend_channel(self.g, destination.g),
+            blend_channel(self.b, destination.b),
+            ((alpha + destination_alpha * (1.0 - alpha)) * 255.0).round() as u8,
+        )
+    }
+
     pub const WHITE: Color = Color::RGB(255, 255, 255);
     pub const BLACK: Color = Color::RGB(0, 0, 0);
     pub const RED: Color = Color::RGB(255, 0, 0);
     pub const GREEN: Color = Color::RGB(0, 255, 0);
     pub const BLUE: Color = Color::RGB(0, 0, 255);
+    pub const TRANSPARENT: Color = Color::RGBA(0, 0, 0, 0);
 }
@@ -0,0 +1,315 @@
+use crate::structs::{BoundingBox, Color, Dimensions, Point};
+
+///
+/// PixelMask definition
+///
+/// Dense row-major set of points inside a bounding rectangle, e.g. the collision points of a
+/// sprite. Lookups index straight into the mask instead of hashing.
+#[derive(Debug, Clone, Default)]
+pub struct PixelMask {
+    // Top-left corner of the mask, relative to the sprite origin
+    offset: Point,
+    dimensions: Dimensions,
+    mask: Vec<bool>,
+    len: usize,
+}
+
+///
+/// PixelBuffer definition
+///
+/// Dense row-major RGBA buffer covering the bounds of a sprite, with a mask of the entries that
+/// hold a pixel.
+#[derive(Debug, Clone, Default)]
+pub struct PixelBuffer {
+    mask: PixelMask,
+    colors: Vec<Color>,
+}
+
+///
+/// PixelMask implementation
+///
+impl PixelMask {
+    /// Mask over the smallest rectangle containing every point
+    pub fn from_points(points: impl IntoIterator<Item = Point>) -> Self {
+        let points: Vec<Point> = points.into_iter().collect();
+        let bounds = match BoundingBox::from_points(&points) {
+            Some(bounds) => bounds,
+            None => return PixelMask::default(),
+        };
+
+        let mut mask = PixelMask {
+            offset: bounds.min,
+            dimensions: Dimensions::new(
+                (bounds.max.x - bounds.min.x + 1) as u32,
+                (bounds.max.y - bounds.min.y + 1) as u32,
+            ),
+            mask: Vec::new(),
+            len: 0,
+        };
+        mask.mask = vec![false; mask.dimensions.width as usize * mask.dimensions.height as usize];
+        for point in points {
+            let index = mask.index(point).unwrap();
+            if !mask.mask[index] {
+                mask.mask[index] = true;
+                mask.len += 1;
+            }
+        }
+
+        mask
+    }
+
+    /// Top-left corner of the mask, relative to the sprite origin
+    pub fn offset(&self) -> Point {
+        self.offset
+    }
+
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.index(point).is_some_and(|index| self.mask[index])
+    }
+
+    /// Every point in the mask, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.mask
+            .iter()
+            .enumerate()
+            .filter(|(_index, set)| **set)
+            .map(|(index, _set)| self.point_at(index))
+    }
+
+    /// Box around every point, or `None` if the mask is empty
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        match self.is_empty() {
+            true => None,
+            false => Some(BoundingBox::from_origin(self.offset, self.dimensions)),
+        }
+    }
+
+    /// The same points, rotated around the sprite origin
+    pub fn rotated(&self, rotate_point: impl Fn(Point) -> Point) -> Self {
+        PixelMask::from_points(self.points().map(rotate_point))
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        let (x, y) = (point.x - self.offset.x, point.y - self.offset.y);
+        if x < 0 || y < 0 || x as u32 >= self.dimensions.width || y as u32 >= self.dimensions.height
+        {
+            return None;
+        }
+
+        Some(y as usize * self.dimensions.width as usize + x as usize)
+    }
+
+    fn point_at(&self, index: usize) -> Point {
+        let width = self.dimensions.width as usize;
+        Point::new(
+            self.offset.x + (index % width) as i32,
+            self.offset.y + (index / width) as i32,
+        )
+    }
+}
+
+///
+/// PixelBuffer implementation
+///
+impl PixelBuffer {
+    /// Buffer over the smallest rectangle containing every pixel
+    pub fn from_pixels(pixels: impl IntoIterator<Item = (Point, Color)>) -> Self {
+        let pixels: Vec<(Point, Color)> = pixels.into_iter().collect();
+        let mask = PixelMask::from_points(pixels.iter().map(|(point, _color)| *point));
+
+        let mut colors = vec![Color::TRANSPARENT; mask.mask.len()];
+        for (point, color) in pixels {
+            colors[mask.index(point).unwrap()] = color;
+        }
+
+        PixelBuffer { mask, colors }
+    }
+
+    /// Which entries of the buffer hold a pixel
+    pub fn mask(&self) -> &PixelMask {
+        &self.mask
+    }
+
+    /// Top-left corner of the buffer, relative to the sprite origin
+    pub fn offset(&self) -> Point {
+        self.mask.offset
+    }
+
+    pub fn dimensions(&self) -> Dimensions {
+        self.mask.dimensions
+    }
+
+    pub fn len(&self) -> usize {
+        self.mask.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mask.is_empty()
+    }
+
+    pub fn get(&self, point: Point) -> Option<Color> {
+        self.mask
+            .index(point)
+            .filter(|index| self.mask.mask[*index])
+            .map(|index| self.colors[index])
+    }
+
+    /// Every pixel with its point (relative to the sprite origin), row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, Color)> + '_ {
+        self.mask
+            .mask
+            .iter()
+            .zip(&self.colors)
+            .enumerate()
+            .filter(|(_index, (set, _color))| **set)
+            .map(|(index, (_set, color))| (self.mask.point_at(index), *color))
+    }
+
+    /// Colors of the whole buffer in row-major order, transparent where there is no pixel
+    pub fn colors(&self) -> &[Color] {
+        &self.colors
+    }
+
+    /// The same pixels, rotated around the sprite origin
+    pub fn rotated(&self, rotate_point: impl Fn(Point) -> Point) -> Self {
+        PixelBuffer::from_pixels(
+            self.iter()
+                .map(|(point, color)| (rotate_point(point), color)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mask_with_negative_offset() {
+        let mask = PixelMask::from_points([
+            Point::new(-3, -2),
+            Point::new(1, -2),
+            Point::new(-1, 0),
+            Point::new(1, 1),
+        ]);
+
+        assert_eq!(mask.offset(), Point::new(-3, -2));
+        assert_eq!(mask.dimensions(), Dimensions::new(5, 4));
+        assert_eq!(mask.len(), 4);
+        assert_eq!(mask.index(Point::new(-3, -2)), Some(0));
+        assert_eq!(mask.index(Point::new(1, -2)), Some(4));
+        assert_eq!(mask.index(Point::new(-1, 0)), Some(12));
+        assert_eq!(mask.index(Point::new(1, 1)), Some(19));
+        assert!(mask.contains(Point::new(-1, 0)));
+        assert!(!mask.contains(Point::new(0, 0)));
+    }
+
+    #[test]
+    fn points_outside_mask_have_no_index() {
+        let mask = PixelMask::from_points([Point::new(-2, -2), Point::new(2, 2)]);
+
+        assert_eq!(mask.index(Point::new(-3, 0)), None);
+        assert_eq!(mask.index(Point::new(3, 0)), None);
+        assert_eq!(mask.index(Point::new(0, -3)), None);
+        assert_eq!(mask.index(Point::new(0, 3)), None);
+        assert!(!mask.contains(Point::new(-3, -3)));
+    }
+
+    #[test]
+    fn duplicate_points_are_counted_once() {
+        let mask = PixelMask::from_points([Point::new(-1, 5), Point::new(-1, 5), Point::new(0, 5)]);
+
+        assert_eq!(mask.len(), 2);
+        assert_eq!(
+            mask.points().collect::<Vec<_>>(),
+            vec![Point::new(-1, 5), Point::new(0, 5)]
+        );
+    }
+
+    #[test]
+    fn points_are_returned_row_by_row() {
+        let mask = PixelMask::from_points([
+            Point::new(2, -1),
+            Point::new(-2, 1),
+            Point::new(0, -1),
+            Point::new(-2, -1),
+        ]);
+
+        assert_eq!(
+            mask.points().collect::<Vec<_>>(),
+            vec![
+                Point::new(-2, -1),
+                Point::new(0, -1),
+                Point::new(2, -1),
+                Point::new(-2, 1),
+            ]
+        );
+        assert_eq!(
+            mask.bounding_box(),
+            Some(BoundingBox::new(Point::new(-2, -1), Point::new(2, 1)))
+        );
+    }
+
+    #[test]
+    fn empty_mask() {
+        let mask = PixelMask::from_points([]);
+
+        assert!(mask.is_empty());
+        assert_eq!(mask.bounding_box(), None);
+        assert_eq!(mask.index(Point::new(0, 0)), None);
+        assert_eq!(mask.points().count(), 0);
+    }
+
+    #[test]
+    fn rotated_mask_moves_offset() {
+        let mask = PixelMask::from_points([Point::new(-2, -1), Point::new(1, -1)]);
+        // Quarter turn clockwise around the origin
+        let rotated = mask.rotated(|point| Point::new(-point.y, point.x));
+
+        assert_eq!(rotated.offset(), Point::new(1, -2));
+        assert_eq!(rotated.dimensions(), Dimensions::new(1, 4));
+        assert_eq!(rotated.len(), 2);
+        assert!(rotated.contains(Point::new(1, -2)));
+        assert!(rotated.contains(Point::new(1, 1)));
+    }
+
+    #[test]
+    fn buffer_with_negative_offset() {
+        let red = Color::RGB(255, 0, 0);
+        let blue = Color::RGB(0, 0, 255);
+        let buffer =
+            PixelBuffer::from_pixels([(Point::new(-1, -1), red), (Point::new(1, 0), blue)]);
+
+        assert_eq!(buffer.offset(), Point::new(-1, -1));
+        assert_eq!(buffer.get(Point::new(-1, -1)), Some(red));
+        assert_eq!(buffer.get(Point::new(1, 0)), Some(blue));
+        assert_eq!(buffer.get(Point::new(0, 0)), None);
+        assert_eq!(buffer.get(Point::new(-2, -1)), None);
+        assert_eq!(
+            buffer.colors(),
+            &[
+                red,
+                Color::TRANSPARENT,
+                Color::TRANSPARENT,
+                Color::TRANSPARENT,
+                Color::TRANSPARENT,
+                blue,
+            ]
+        );
+        assert_eq!(
+            buffer.iter().collect::<Vec<_>>(),
+            vec![(Point::new(-1, -1), red), (Point::new(1, 0), blue)]
+        );
+    }
+}
@@ -1,9 +1,13 @@
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PlayerInput {
     KeyDown(Key),
+    // A printable character typed by the player, for text entry in menus
+    TextInput(char),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Key {
     Num1,
     Num2,
@@ -19,4 +23,24 @@ pub enum Key {
     J,
     K,
     L,
+    // Menu navigation
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Backspace,
+    // Opens and closes the pause menu
+    Pause,
+}
+
+impl Key {
+    /// Keys that also type a character. While a text widget has focus they arrive as
+    /// `PlayerInput::TextInput` instead.
+    pub fn types_character(self) -> bool {
+        !matches!(
+            self,
+            Key::Up | Key::Down | Key::Left | Key::Right | Key::Enter | Key::Backspace
+        )
+    }
 }
@@ -0,0 +1,16 @@
+///
+/// RenderLayer definition
+///
+/// Layers are drawn in declaration order, so later layers appear on top. Within a layer,
+/// objects are ordered by their z-index.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RenderLayer {
+    Background,
+    Terrain,
+    Pickups,
+    Shadows,
+    Projectiles,
+    Actors,
+    Effects,
+    Ui,
+}
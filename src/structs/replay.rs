@@ -0,0 +1,55 @@
+use crate::structs::{Dimensions, PlayerInput};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+///
+/// Replay definition
+///
+/// Everything needed to play a match back deterministically: the map and viewport sizes, the
+/// seed for the game's random number generator, and the input (if any) for every tick.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub map_width: u32,
+    pub map_height: u32,
+    pub viewport_width: u32,
+    pub viewport_height: u32,
+    pub seed: u64,
+    pub frames: Vec<Option<PlayerInput>>,
+}
+
+///
+/// Replay implementation
+///
+impl Replay {
+    pub fn new(map: Dimensions, viewport: Dimensions, seed: u64) -> Self {
+        Self {
+            map_width: map.width,
+            map_height: map.height,
+            viewport_width: viewport.width,
+            viewport_height: viewport.height,
+            seed,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn record_frame(&mut self, input: Option<PlayerInput>) {
+        self.frames.push(input);
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read replay {}: {}", path.display(), err))?;
+
+        serde_json::from_str(&contents)
+            .map_err(|err| format!("Invalid replay {}: {}", path.display(), err))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string(self)
+            .map_err(|err| format!("Failed to serialize replay: {}", err))?;
+
+        fs::write(path, contents)
+            .map_err(|err| format!("Failed to write replay {}: {}", path.display(), err))
+    }
+}
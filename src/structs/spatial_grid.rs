@@ -0,0 +1,90 @@
+use crate::structs::{BoundingBox, Point};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+// Cells roughly the size of the larger sprites, so most objects touch only a few cells
+pub const DEFAULT_GRID_CELL_SIZE: u32 = 64;
+
+///
+/// SpatialGrid definition
+///
+/// Uniform grid broadphase. Entries are registered in every cell their bounding box overlaps,
+/// so a query only has to look at the cells around the area it covers.
+#[derive(Debug, Clone)]
+pub struct SpatialGrid<K> {
+    cell_size: i32,
+    cells: HashMap<(i32, i32), Vec<(K, BoundingBox)>>,
+}
+
+///
+/// SpatialGrid implementation
+///
+impl<K: Copy + Eq + Hash> SpatialGrid<K> {
+    pub fn new(cell_size: u32) -> Self {
+        Self {
+            cell_size: cell_size.max(1) as i32,
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    pub fn insert(&mut self, key: K, bounds: BoundingBox) {
+        for cell in self.cells_overlapping(&bounds) {
+            self.cells.entry(cell).or_default().push((key, bounds));
+        }
+    }
+
+    /// Registers a large or irregular shape (e.g. terrain) by the points it is made of. Each
+    /// cell only gets the bounds of the points inside it, instead of the bounds of the whole
+    /// shape.
+    pub fn insert_points(&mut self, key: K, points: impl IntoIterator<Item = Point>) {
+        let mut points_per_cell: HashMap<(i32, i32), BoundingBox> = HashMap::new();
+        for point in points {
+            let point_bounds = BoundingBox::new(point, point);
+            points_per_cell
+                .entry(self.cell_of(point))
+                .and_modify(|bounds| *bounds = bounds.union(&point_bounds))
+                .or_insert(point_bounds);
+        }
+
+        for (cell, bounds) in points_per_cell {
+            self.cells.entry(cell).or_default().push((key, bounds));
+        }
+    }
+
+    /// Keys of every entry whose bounds overlap `bounds`, each reported once
+    pub fn query(&self, bounds: &BoundingBox) -> Vec<K> {
+        let mut seen: HashSet<K> = HashSet::new();
+
+        self.cells_overlapping(bounds)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(|(_key, entry_bounds)| entry_bounds.intersects(bounds))
+            .filter(|(key, _entry_bounds)| seen.insert(*key))
+            .map(|(key, _entry_bounds)| *key)
+            .collect()
+    }
+
+    fn cell_of(&self, point: Point) -> (i32, i32) {
+        (
+            point.x.div_euclid(self.cell_size),
+            point.y.div_euclid(self.cell_size),
+        )
+    }
+
+    fn cells_overlapping(&self, bounds: &BoundingBox) -> impl Iterator<Item = (i32, i32)> {
+        let (min_x, min_y) = self.cell_of(bounds.min);
+        let (max_x, max_y) = self.cell_of(bounds.max);
+
+        (min_y..=max_y).flat_map(move |y| (min_x..=max_x).map(move |x| (x, y)))
+    }
+}
+
+impl<K: Copy + Eq + Hash> Default for SpatialGrid<K> {
+    fn default() -> Self {
+        SpatialGrid::new(DEFAULT_GRID_CELL_SIZE)
+    }
+}
@@ -1,175 +1,252 @@
-use crate::structs::{Color, Pixel, Point, Rotation};
-use image::io::Reader as ImageReader;
-use image::{GenericImageView, Pixel as ImagePixel};
+use crate::structs::{
+    Color, GlyphMetadata, HitboxMetadata, Pixel, PixelBuffer, PixelMask, Point, Rotation,
+    SpriteError,
+};
+use crate::util::{embedded_file, sprite_dir, warn, EMBEDDED_ASSET_DIR};
+use image::{DynamicImage, GenericImageView};
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufReader, Read};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 
-pub const SPRITE_RESOURCE_DIR: &'static str = "resources/sprites/";
+pub const DEFAULT_SPRITE_NAME: &str = "default";
+
+// Sprites loaded through `Sprite::shared`, keyed by file name
+static SHARED_SPRITES: OnceLock<Mutex<HashMap<String, Sprite>>> = OnceLock::new();
+static ID_COUNTER: AtomicU32 = AtomicU32::new(0);
 
 ///
 /// Structs
 ///
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Sprite {
+    // Unique per created sprite and kept by its clones, which share its pixels. Renderers cache
+    // per id and rotation
+    id: u32,
+    // Identifies the sprite's image (e.g. its file name)
+    name: String,
+    rotation: Rotation,
     dimensions: Dimensions,
-    origin: Point,
-    original_sprite_data: HashMap<Point, Pixel>,
-    sprite_data: HashMap<Point, Pixel>,
+    // Pixels and collision points for every rotation, indexed by `Rotation::index`. Computed
+    // once when the sprite is created and shared between clones, so turning costs nothing.
+    rotations: Arc<[SpriteRotation; 4]>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug)]
+struct SpriteRotation {
+    pixels: PixelBuffer,
+    collision_points: PixelMask,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Deserialize)]
 pub struct Dimensions {
     pub width: u32,
     pub height: u32,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct SpriteMetadata {
     pub dimensions: Dimensions,
     pub origin: Point,
+    #[serde(default)]
+    pub hitbox: Option<HitboxMetadata>,
+    // Only present for bitmap fonts, describing how the image is divided into glyphs
+    #[serde(default)]
+    pub glyphs: Option<GlyphMetadata>,
 }
 
 ///
 /// Sprite implementation
 ///
 impl Sprite {
-    pub fn new(sprite_data: HashMap<Point, Pixel>) -> Self {
-        let (mut x_min, mut x_max): (i32, i32) = (0, 0);
-        let (mut y_min, mut y_max): (i32, i32) = (0, 0);
-
-        // Find width and height of sprite
-        for (point, _pixel) in sprite_data.iter() {
-            if point.x < x_min {
-                x_min = point.x;
-            } else if point.x > x_max {
-                x_max = point.x
-            }
-            if point.y < y_min {
-                y_min = point.y
-            } else if point.y > y_max {
-                y_max = point.y
-            }
-        }
-        let width: u32 = u32::try_from(x_max - x_min).unwrap();
-        let height: u32 = u32::try_from(y_max - y_min).unwrap();
+    pub fn new(name: &str, sprite_data: impl IntoIterator<Item = (Point, Pixel)>) -> Self {
+        let pixels = PixelBuffer::from_pixels(
+            sprite_data
+                .into_iter()
+                .map(|(point, pixel)| (point, pixel.color)),
+        );
+        let collision_points = pixels.mask().clone();
 
         Sprite {
-            dimensions: Dimensions::new(width, height),
-            origin: Point::new(0, 0),
-            original_sprite_data: sprite_data.clone(),
-            sprite_data,
+            id: Sprite::get_id(),
+            name: name.to_owned(),
+            rotation: Rotation::default(),
+            dimensions: pixels.dimensions(),
+            rotations: Sprite::build_rotations(pixels, collision_points),
         }
     }
 
-    pub fn new_from_file(file_name: &str) -> Self {
-        let metadata_filename = SPRITE_RESOURCE_DIR.to_owned() + file_name + ".json";
-        let image_filename = SPRITE_RESOURCE_DIR.to_owned() + file_name + ".png";
+    /// Loads a sprite from the asset root, falling back to the embedded copy (if compiled in)
+    /// when the files are missing on disk.
+    pub fn new_from_file(file_name: &str) -> Result<Self, SpriteError> {
+        Sprite::new_from_file_with_metadata(file_name).map(|(sprite, _metadata)| sprite)
+    }
 
-        // Retrieve metadata
-        let metadata_file = File::options().read(true).open(metadata_filename).unwrap();
+    /// Same as `new_from_file`, but also returns the metadata the sprite was built from. Used for
+    /// sections the sprite itself does not keep, such as font glyphs.
+    pub fn new_from_file_with_metadata(
+        file_name: &str,
+    ) -> Result<(Self, SpriteMetadata), SpriteError> {
+        match Sprite::load(file_name, |file| read_dir_file(&sprite_dir(), file)) {
+            Err(SpriteError::MissingFile(_))
+                if embedded_file(&metadata_file_name(file_name)).is_some() =>
+            {
+                Sprite::load(file_name, read_embedded_file)
+            }
+            result => result,
+        }
+    }
+
+    pub fn new_from_dir(dir: &Path, file_name: &str) -> Result<Self, SpriteError> {
+        Sprite::load(file_name, |file| read_dir_file(dir, file)).map(|(sprite, _metadata)| sprite)
+    }
 
-        let mut metadata_contents = String::new();
-        BufReader::new(metadata_file)
-            .read_to_string(&mut metadata_contents)
-            .unwrap();
+    pub fn new_from_embedded(file_name: &str) -> Result<Self, SpriteError> {
+        Sprite::load(file_name, read_embedded_file).map(|(sprite, _metadata)| sprite)
+    }
 
-        // Deserialize metadata
-        let metadata: SpriteMetadata =
-            serde_json::from_str(&metadata_contents).expect("Error deserializing sprite metadata");
+    /// Builds a sprite from its metadata, image and optional hitbox mask. `read_file` returns the
+    /// path and contents of a file in the sprite's directory.
+    fn load<F>(file_name: &str, read_file: F) -> Result<(Self, SpriteMetadata), SpriteError>
+    where
+        F: Fn(&str) -> Result<(PathBuf, Vec<u8>), SpriteError>,
+    {
+        let metadata = read_metadata(file_name, &read_file)?;
         let dimensions = metadata.dimensions;
         let origin = metadata.origin;
 
         // Decode image info
-        let img = ImageReader::open(image_filename).unwrap().decode().unwrap();
-
-        let mut sprite_data: HashMap<Point, Pixel> = HashMap::new();
-        img.pixels()
-            // Filter out points that contain no info
-            .filter(|(_x, _y, (rgba))| rgba[0] != 0 || rgba[1] != 0 || rgba[2] != 0 || rgba[3] != 0)
-            // For each point, translate it relative to the metadata.origin
-            .for_each(|(x, y, (rgba))| {
-                let translated_x = (x as i32) - origin.x;
-                let translated_y = (y as i32) - origin.y;
-
-                sprite_data.insert(
-                    Point::new(translated_x, translated_y),
-                    Pixel::new(Color::RGBA(rgba[0], rgba[1], rgba[2], rgba[3])),
-                );
-            });
-
-        Self {
+        let img = decode_image(read_file(&image_file_name(file_name))?, dimensions)?;
+
+        let pixels = PixelBuffer::from_pixels(
+            img.pixels()
+                // Filter out points that contain no info
+                .filter(|(_x, _y, rgba)| {
+                    rgba[0] != 0 || rgba[1] != 0 || rgba[2] != 0 || rgba[3] != 0
+                })
+                // For each point, translate it relative to the metadata.origin
+                .map(|(x, y, rgba)| {
+                    (
+                        Point::new(x as i32 - origin.x, y as i32 - origin.y),
+                        Color::RGBA(rgba[0], rgba[1], rgba[2], rgba[3]),
+                    )
+                }),
+        );
+
+        // Without a hitbox, every visible pixel is solid
+        let collision_points = match &metadata.hitbox {
+            None => pixels.mask().clone(),
+            Some(HitboxMetadata::Mask(mask_name)) => PixelMask::from_points(
+                decode_image(read_file(&image_file_name(mask_name))?, dimensions)?
+                    .pixels()
+                    .filter(|(_x, _y, rgba)| rgba[3] != 0)
+                    .map(|(x, y, _rgba)| Point::new(x as i32 - origin.x, y as i32 - origin.y)),
+            ),
+            Some(HitboxMetadata::Shapes(shapes)) => PixelMask::from_points(
+                shapes
+                    .iter()
+                    .flat_map(|shape| shape.points())
+                    .map(|point| Point::new(point.x - origin.x, point.y - origin.y)),
+            ),
+        };
+
+        let sprite = Self {
+            id: Sprite::get_id(),
+            name: file_name.to_owned(),
+            rotation: Rotation::default(),
             dimensions,
-            origin,
-            original_sprite_data: sprite_data.clone(),
-            sprite_data,
-        }
-    }
+            rotations: Sprite::build_rotations(pixels, collision_points),
+        };
 
-    pub fn dimensions(&self) -> &Dimensions {
-        &self.dimensions
+        Ok((sprite, metadata))
     }
 
-    pub fn pixels(&self) -> &HashMap<Point, Pixel> {
-        &self.sprite_data
+    /// Loads a sprite from file, falling back to `Sprite::default()` if it cannot be loaded.
+    pub fn new_from_file_or_default(file_name: &str) -> Self {
+        Sprite::new_from_file(file_name).unwrap_or_else(|err| {
+            warn(format!("Failed to load sprite '{}': {}", file_name, err));
+            Sprite::default()
+        })
     }
 
-    pub fn rotate_sprite_around_origin(&mut self, rotation: Rotation) {
-        // Represents the center-point of the image to rotate
-        // The reason for this is that all other logic assumes the origin is at (0, 0) of the
-        // image map. For rotation however, we need to translate the points relative to the center
-        // of the image. For example, in a 4x4 grid, the center point would be (2, 2) and the point
-        // (0, 0) would now be represented as (-2, -2)
-        // let center_width: u32 = self.dimensions.width / 2;
-        // let center_height: u32 = self.dimensions.height / 2;
+    /// Same as `new_from_file_or_default`, but each file is only loaded once. Later calls return
+    /// a copy that shares the pixels of every rotation with the first, facing up.
+    pub fn shared(file_name: &str) -> Self {
+        let mut shared_sprites = SHARED_SPRITES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        shared_sprites
+            .entry(file_name.to_owned())
+            .or_insert_with(|| Sprite::new_from_file_or_default(file_name))
+            .clone()
+    }
 
-        match rotation {
-            Rotation::Up => self.sprite_data = self.original_sprite_data.clone(),
-            Rotation::Left => {
-                let mut new_sprite_data: HashMap<Point, Pixel> = HashMap::new();
+    pub fn id(&self) -> u32 {
+        self.id
+    }
 
-                self.original_sprite_data.iter().for_each(|(point, pixel)| {
-                    let new_x = point.y;
-                    let new_y = -point.x;
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 
-                    new_sprite_data.insert(Point::new(new_x, new_y), *pixel);
-                });
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
 
-                self.sprite_data = new_sprite_data;
-            }
-            Rotation::Right => {
-                let mut new_sprite_data: HashMap<Point, Pixel> = HashMap::new();
+    pub fn dimensions(&self) -> &Dimensions {
+        &self.dimensions
+    }
 
-                self.original_sprite_data.iter().for_each(|(point, pixel)| {
-                    let new_x = -point.y;
-                    let new_y = point.x;
+    /// Top-left corner (relative to the origin) and size of the area covered by the current
+    /// (rotated) pixels
+    pub fn bounds(&self) -> (Point, Dimensions) {
+        (self.pixels().offset(), self.pixels().dimensions())
+    }
 
-                    new_sprite_data.insert(Point::new(new_x, new_y), *pixel);
-                });
+    pub fn pixels(&self) -> &PixelBuffer {
+        &self.rotations[self.rotation.index()].pixels
+    }
 
-                self.sprite_data = new_sprite_data;
-            }
-            Rotation::Down => {
-                let mut new_sprite_data: HashMap<Point, Pixel> = HashMap::new();
+    /// Points (relative to the origin) that take part in collision
+    pub fn collision_points(&self) -> &PixelMask {
+        &self.rotations[self.rotation.index()].collision_points
+    }
 
-                self.original_sprite_data.iter().for_each(|(point, pixel)| {
-                    let new_x = point.x;
-                    let new_y = -point.y;
+    fn get_id() -> u32 {
+        ID_COUNTER.fetch_add(1, Ordering::Relaxed)
+    }
 
-                    new_sprite_data.insert(Point::new(new_x, new_y), *pixel);
-                });
+    /// Switches to the precomputed pixels for `rotation`
+    pub fn rotate_sprite_around_origin(&mut self, rotation: Rotation) {
+        self.rotation = rotation;
+    }
 
-                self.sprite_data = new_sprite_data;
+    /// Rotates the upright pixels and collision points into every supported rotation. Points are
+    /// rotated around the sprite's origin.
+    fn build_rotations(
+        pixels: PixelBuffer,
+        collision_points: PixelMask,
+    ) -> Arc<[SpriteRotation; 4]> {
+        Arc::new(Rotation::ALL.map(|rotation| {
+            let rotate_point = |point| Sprite::rotate_point(point, rotation);
+            SpriteRotation {
+                pixels: pixels.rotated(rotate_point),
+                collision_points: collision_points.rotated(rotate_point),
             }
-        }
+        }))
     }
 
-    fn relocate_point_around_origin(new_origin: Point, point: Point) -> (i32, i32) {
-        let new_x: i32 = i32::try_from(point.x).unwrap() - i32::try_from(new_origin.x).unwrap();
-        let new_y: i32 = i32::try_from(point.y).unwrap() - i32::try_from(new_origin.y).unwrap();
-
-        (new_x, new_y)
+    fn rotate_point(point: Point, rotation: Rotation) -> Point {
+        match rotation {
+            Rotation::Up => point,
+            Rotation::Left => Point::new(point.y, -point.x),
+            Rotation::Right => Point::new(-point.y, point.x),
+            Rotation::Down => Point::new(point.x, -point.y),
+        }
     }
 
     fn build_default_sprite() -> HashMap<Point, Pixel> {
@@ -209,7 +286,7 @@ impl Sprite {
 
 impl Default for Sprite {
     fn default() -> Self {
-        Sprite::new(Sprite::build_default_sprite())
+        Sprite::new(DEFAULT_SPRITE_NAME, Sprite::build_default_sprite())
     }
 }
 
@@ -221,3 +298,60 @@ impl Dimensions {
         Dimensions { width, height }
     }
 }
+
+fn read_dir_file(dir: &Path, file_name: &str) -> Result<(PathBuf, Vec<u8>), SpriteError> {
+    let path = dir.join(file_name);
+    fs::read(&path)
+        .map(|contents| (path.clone(), contents))
+        .map_err(|err| SpriteError::from_io(path, err))
+}
+
+fn read_embedded_file(file_name: &str) -> Result<(PathBuf, Vec<u8>), SpriteError> {
+    let path = Path::new(EMBEDDED_ASSET_DIR).join(file_name);
+    embedded_file(file_name)
+        .map(|contents| (path.clone(), contents.to_vec()))
+        .ok_or(SpriteError::MissingFile(path))
+}
+
+/// Retrieves and deserializes the metadata of a sprite
+fn read_metadata<F>(file_name: &str, read_file: F) -> Result<SpriteMetadata, SpriteError>
+where
+    F: Fn(&str) -> Result<(PathBuf, Vec<u8>), SpriteError>,
+{
+    let (metadata_filename, metadata_contents) = read_file(&metadata_file_name(file_name))?;
+    serde_json::from_slice(&metadata_contents).map_err(|err| SpriteError::InvalidMetadata {
+        path: metadata_filename,
+        source: err,
+    })
+}
+
+fn metadata_file_name(file_name: &str) -> String {
+    format!("{}.json", file_name)
+}
+
+fn image_file_name(file_name: &str) -> String {
+    format!("{}.png", file_name)
+}
+
+/// Decodes an image, checking it has the dimensions declared in the sprite metadata.
+fn decode_image(
+    (image_filename, image_contents): (PathBuf, Vec<u8>),
+    dimensions: Dimensions,
+) -> Result<DynamicImage, SpriteError> {
+    let img = image::load_from_memory(&image_contents).map_err(|err| SpriteError::ImageDecode {
+        path: image_filename.clone(),
+        source: err,
+    })?;
+
+    // Metadata must describe the image it belongs to
+    let image_dimensions = Dimensions::new(img.width(), img.height());
+    if image_dimensions != dimensions {
+        return Err(SpriteError::DimensionMismatch {
+            path: image_filename,
+            metadata: dimensions,
+            image: image_dimensions,
+        });
+    }
+
+    Ok(img)
+}
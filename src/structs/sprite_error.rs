@@ -0,0 +1,99 @@
+use crate::structs::Dimensions;
+use std::path::PathBuf;
+
+///
+/// SpriteError definition
+///
+#[derive(Debug)]
+pub enum SpriteError {
+    // The metadata or image file does not exist
+    MissingFile(PathBuf),
+    // The file exists but could not be read
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    // The metadata file is not valid sprite metadata JSON
+    InvalidMetadata {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    // The image file could not be decoded
+    ImageDecode {
+        path: PathBuf,
+        source: image::ImageError,
+    },
+    // The metadata describes a different size than the decoded image
+    DimensionMismatch {
+        path: PathBuf,
+        metadata: Dimensions,
+        image: Dimensions,
+    },
+    // A font's metadata has no `glyphs` section
+    MissingGlyphs(PathBuf),
+}
+
+///
+/// SpriteError implementation
+///
+impl SpriteError {
+    pub fn from_io(path: PathBuf, source: std::io::Error) -> Self {
+        match source.kind() {
+            std::io::ErrorKind::NotFound => SpriteError::MissingFile(path),
+            _ => SpriteError::Io { path, source },
+        }
+    }
+}
+
+impl std::fmt::Display for SpriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SpriteError::MissingFile(path) => {
+                write!(f, "sprite file not found: {}", path.display())
+            }
+            SpriteError::Io { path, source } => {
+                write!(f, "failed to read {}: {}", path.display(), source)
+            }
+            SpriteError::InvalidMetadata { path, source } => {
+                write!(
+                    f,
+                    "invalid sprite metadata in {}: {}",
+                    path.display(),
+                    source
+                )
+            }
+            SpriteError::ImageDecode { path, source } => {
+                write!(f, "failed to decode {}: {}", path.display(), source)
+            }
+            SpriteError::MissingGlyphs(path) => {
+                write!(f, "{}: font metadata has no glyphs section", path.display())
+            }
+            SpriteError::DimensionMismatch {
+                path,
+                metadata,
+                image,
+            } => write!(
+                f,
+                "{}: metadata dimensions {}x{} do not match image dimensions {}x{}",
+                path.display(),
+                metadata.width,
+                metadata.height,
+                image.width,
+                image.height
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SpriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SpriteError::Io { source, .. } => Some(source),
+            SpriteError::InvalidMetadata { source, .. } => Some(source),
+            SpriteError::ImageDecode { source, .. } => Some(source),
+            SpriteError::MissingFile(_)
+            | SpriteError::MissingGlyphs(_)
+            | SpriteError::DimensionMismatch { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use crate::structs::Point;
+
+///
+/// SweepHit definition
+///
+/// First contact found when moving an object along a path
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SweepHit {
+    // Origin at which the object first touches the obstacle
+    pub origin: Point,
+    // Last origin on the path before the contact, where the object can safely stop
+    pub last_free_origin: Point,
+    // A world pixel shared by the object and the obstacle at `origin`
+    pub contact_point: Point,
+}
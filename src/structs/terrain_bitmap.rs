@@ -0,0 +1,472 @@
+use crate::structs::{BoundingBox, Color, Dimensions, Point};
+
+const BITS_PER_WORD: u32 = u64::BITS;
+
+///
+/// TerrainSpan definition
+///
+/// Run of solid pixels of the same color on a single row, in bitmap coordinates.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TerrainSpan {
+    pub start: Point,
+    pub length: u32,
+    pub color: Color,
+}
+
+///
+/// TerrainBitmap definition
+///
+/// Static terrain stored as one occupancy bit per pixel plus a palette, instead of a map entry
+/// per pixel.
+#[derive(Debug, Clone)]
+pub struct TerrainBitmap {
+    dimensions: Dimensions,
+    // Occupancy words per row; every row starts on a new word
+    words_per_row: usize,
+    occupancy: Vec<u64>,
+    palette: Vec<Color>,
+    // Palette index per pixel, only allocated once a second color is used
+    palette_indices: Option<Vec<u16>>,
+}
+
+///
+/// TerrainBitmap implementation
+///
+impl TerrainBitmap {
+    /// Empty bitmap where every pixel will be drawn in `color` unless set otherwise
+    pub fn new(dimensions: Dimensions, color: Color) -> Self {
+        let words_per_row = dimensions.width.div_ceil(BITS_PER_WORD) as usize;
+
+        Self {
+            dimensions,
+            words_per_row,
+            occupancy: vec![0; words_per_row * dimensions.height as usize],
+            palette: vec![color],
+            palette_indices: None,
+        }
+    }
+
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    pub fn palette(&self) -> &[Color] {
+        &self.palette
+    }
+
+    pub fn is_solid(&self, point: Point) -> bool {
+        match self.bit_index(point) {
+            Some((word, bit)) => self.occupancy[word] & (1 << bit) != 0,
+            None => false,
+        }
+    }
+
+    /// Color of the solid pixel at `point`, or `None` if it is empty
+    pub fn color(&self, point: Point) -> Option<Color> {
+        match self.is_solid(point) {
+            true => Some(self.palette[self.palette_index(point)]),
+            false => None,
+        }
+    }
+
+    /// Marks `point` as solid. Points outside the bitmap are ignored.
+    pub fn set(&mut self, point: Point, color: Color) {
+        let (word, bit) = match self.bit_index(point) {
+            Some(index) => index,
+            None => return,
+        };
+
+        self.occupancy[word] |= 1 << bit;
+        let index = self.palette_entry(color);
+        if let Some(indices) = &mut self.palette_indices {
+            indices[point.y as usize * self.dimensions.width as usize + point.x as usize] = index;
+        }
+    }
+
+    pub fn clear(&mut self, point: Point) {
+        if let Some((word, bit)) = self.bit_index(point) {
+            self.occupancy[word] &= !(1 << bit);
+        }
+    }
+
+    /// Marks every point of the rectangle at `origin` as solid, clipped to the bitmap
+    pub fn fill_rect(&mut self, origin: Point, dimensions: Dimensions, color: Color) {
+        let x_range =
+            origin.x.max(0)..(origin.x + dimensions.width as i32).min(self.dimensions.width as i32);
+        let y_range = origin.y.max(0)
+            ..(origin.y + dimensions.height as i32).min(self.dimensions.height as i32);
+
+        for y in y_range {
+            for x in x_range.clone() {
+                self.set(Point::new(x, y), color);
+            }
+        }
+    }
+
+    /// Box around every solid pixel, or `None` if the bitmap is empty
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        (0..self.dimensions.height as i32)
+            .flat_map(|y| self.row_spans(y))
+            .map(|span| {
+                BoundingBox::new(
+                    span.start,
+                    Point::new(span.start.x + span.length as i32 - 1, span.start.y),
+                )
+            })
+            .reduce(|bounds, span_bounds| bounds.union(&span_bounds))
+    }
+
+    /// Runs of solid pixels on row `y`, from left to right. Empty words are skipped whole.
+    pub fn row_spans(&self, y: i32) -> Vec<TerrainSpan> {
+        let mut spans = Vec::new();
+        if y < 0 || y >= self.dimensions.height as i32 {
+            return spans;
+        }
+
+        let row_start = y as usize * self.words_per_row;
+        let row = &self.occupancy[row_start..row_start + self.words_per_row];
+        let mut run_start: Option<u32> = None;
+
+        for (word_index, word) in row.iter().enumerate() {
+            let word_start = word_index as u32 * BITS_PER_WORD;
+            let mut bit = 0;
+
+            while bit < BITS_PER_WORD {
+                let remaining = word >> bit;
+                bit += match run_start {
+                    // Inside a run: it continues for as many set bits as follow
+                    Some(_) => (!remaining).trailing_zeros(),
+                    // Outside a run: skip the clear bits
+                    None => remaining.trailing_zeros(),
+                }
+                .min(BITS_PER_WORD - bit);
+
+                if bit < BITS_PER_WORD {
+                    match run_start {
+                        Some(start) => {
+                            self.push_spans(&mut spans, y, start, word_start + bit);
+                            run_start = None;
+                        }
+                        None => run_start = Some(word_start + bit),
+                    }
+                }
+            }
+        }
+
+        if let Some(start) = run_start {
+            self.push_spans(&mut spans, y, start, self.dimensions.width);
+        }
+
+        spans
+    }
+
+    /// Every solid pixel, row by row
+    pub fn solid_points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.dimensions.height as i32)
+            .flat_map(|y| self.row_spans(y))
+            .flat_map(|span| {
+                (0..span.length as i32).map(move |x| Point::new(span.start.x + x, span.start.y))
+            })
+    }
+
+    /// Solid pixels merged into as few rectangles of a single color as spans allow, for drawing
+    /// with one fill per rectangle. Spans are merged with identical spans on the rows below.
+    pub fn rects(&self) -> Vec<(Point, Dimensions, Color)> {
+        let mut finished = Vec::new();
+        // Rectangles that may still grow downwards
+        let mut open: Vec<(Point, Dimensions, Color)> = Vec::new();
+
+        for y in 0..self.dimensions.height as i32 {
+            let mut still_open = Vec::new();
+
+            for span in self.row_spans(y) {
+                let position = open.iter().position(|(origin, dimensions, color)| {
+                    origin.x == span.start.x
+                        && dimensions.width == span.length
+                        && *color == span.color
+                });
+
+                match position {
+                    Some(position) => {
+                        let (origin, dimensions, color) = open.swap_remove(position);
+                        still_open.push((
+                            origin,
+                            Dimensions::new(dimensions.width, dimensions.height + 1),
+                            color,
+                        ));
+                    }
+                    None => {
+                        still_open.push((span.start, Dimensions::new(span.length, 1), span.color))
+                    }
+                }
+            }
+
+            finished.append(&mut open);
+            open = still_open;
+        }
+
+        finished.append(&mut open);
+        finished
+    }
+
+    fn bit_index(&self, point: Point) -> Option<(usize, u32)> {
+        if point.x < 0
+            || point.y < 0
+            || point.x as u32 >= self.dimensions.width
+            || point.y as u32 >= self.dimensions.height
+        {
+            return None;
+        }
+
+        let x = point.x as u32;
+        Some((
+            point.y as usize * self.words_per_row + (x / BITS_PER_WORD) as usize,
+            x % BITS_PER_WORD,
+        ))
+    }
+
+    fn palette_index(&self, point: Point) -> usize {
+        match &self.palette_indices {
+            Some(indices) => {
+                indices[point.y as usize * self.dimensions.width as usize + point.x as usize]
+                    as usize
+            }
+            None => 0,
+        }
+    }
+
+    /// Index of `color` in the palette, adding it if needed
+    fn palette_entry(&mut self, color: Color) -> u16 {
+        if let Some(index) = self.palette.iter().position(|entry| *entry == color) {
+            return index as u16;
+        }
+
+        assert!(
+            self.palette.len() <= u16::MAX as usize,
+            "TerrainBitmap supports at most 65536 colors"
+        );
+        if self.palette_indices.is_none() {
+            // Every pixel set so far used the only color
+            let pixel_count = self.dimensions.width as usize * self.dimensions.height as usize;
+            self.palette_indices = Some(vec![0; pixel_count]);
+        }
+        self.palette.push(color);
+
+        (self.palette.len() - 1) as u16
+    }
+
+    /// Adds the run of solid pixels from `start` to `end` (exclusive) on row `y`, split wherever
+    /// the color changes
+    fn push_spans(&self, spans: &mut Vec<TerrainSpan>, y: i32, start: u32, end: u32) {
+        if self.palette_indices.is_none() {
+            spans.push(TerrainSpan {
+                start: Point::new(start as i32, y),
+                length: end - start,
+                color: self.palette[0],
+            });
+            return;
+        }
+
+        let mut span_start = start;
+
+        for x in start + 1..=end {
+            let color = self.palette[self.palette_index(Point::new(span_start as i32, y))];
+            let ends_here =
+                x == end || self.palette[self.palette_index(Point::new(x as i32, y))] != color;
+
+            if ends_here {
+                spans.push(TerrainSpan {
+                    start: Point::new(span_start as i32, y),
+                    length: x - span_start,
+                    color,
+                });
+                span_start = x;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Color = Color::RGB(255, 0, 0);
+    const BLUE: Color = Color::RGB(0, 0, 255);
+
+    #[test]
+    fn span_crossing_word_edge_is_not_split() {
+        let mut bitmap = TerrainBitmap::new(Dimensions::new(200, 2), RED);
+        bitmap.fill_rect(Point::new(60, 1), Dimensions::new(10, 1), RED);
+        bitmap.fill_rect(Point::new(120, 1), Dimensions::new(16, 1), RED);
+
+        assert_eq!(
+            bitmap.row_spans(1),
+            vec![
+                TerrainSpan {
+                    start: Point::new(60, 1),
+                    length: 10,
+                    color: RED,
+                },
+                TerrainSpan {
+                    start: Point::new(120, 1),
+                    length: 16,
+                    color: RED,
+                },
+            ]
+        );
+        assert!(bitmap.row_spans(0).is_empty());
+    }
+
+    #[test]
+    fn span_covering_whole_words() {
+        let mut bitmap = TerrainBitmap::new(Dimensions::new(256, 1), RED);
+        bitmap.fill_rect(Point::new(64, 0), Dimensions::new(64, 1), RED);
+        bitmap.fill_rect(Point::new(192, 0), Dimensions::new(64, 1), RED);
+
+        assert_eq!(
+            bitmap.row_spans(0),
+            vec![
+                TerrainSpan {
+                    start: Point::new(64, 0),
+                    length: 64,
+                    color: RED,
+                },
+                TerrainSpan {
+                    start: Point::new(192, 0),
+                    length: 64,
+                    color: RED,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn full_row_with_width_not_a_multiple_of_word_size() {
+        let mut bitmap = TerrainBitmap::new(Dimensions::new(100, 3), RED);
+        bitmap.fill_rect(Point::new(0, 1), Dimensions::new(100, 1), RED);
+
+        assert_eq!(
+            bitmap.row_spans(1),
+            vec![TerrainSpan {
+                start: Point::new(0, 1),
+                length: 100,
+                color: RED,
+            }]
+        );
+        assert!(bitmap.row_spans(0).is_empty());
+        assert!(bitmap.row_spans(2).is_empty());
+    }
+
+    #[test]
+    fn run_to_end_of_row() {
+        let mut bitmap = TerrainBitmap::new(Dimensions::new(130, 1), RED);
+        bitmap.set(Point::new(0, 0), RED);
+        bitmap.fill_rect(Point::new(120, 0), Dimensions::new(10, 1), RED);
+
+        assert_eq!(
+            bitmap.row_spans(0),
+            vec![
+                TerrainSpan {
+                    start: Point::new(0, 0),
+                    length: 1,
+                    color: RED,
+                },
+                TerrainSpan {
+                    start: Point::new(120, 0),
+                    length: 10,
+                    color: RED,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn spans_are_split_where_color_changes() {
+        let mut bitmap = TerrainBitmap::new(Dimensions::new(100, 1), RED);
+        bitmap.fill_rect(Point::new(10, 0), Dimensions::new(10, 1), RED);
+        bitmap.fill_rect(Point::new(20, 0), Dimensions::new(10, 1), BLUE);
+        bitmap.fill_rect(Point::new(30, 0), Dimensions::new(5, 1), RED);
+        // Color change on a word edge
+        bitmap.fill_rect(Point::new(60, 0), Dimensions::new(4, 1), RED);
+        bitmap.fill_rect(Point::new(64, 0), Dimensions::new(4, 1), BLUE);
+
+        assert_eq!(
+            bitmap.row_spans(0),
+            vec![
+                TerrainSpan {
+                    start: Point::new(10, 0),
+                    length: 10,
+                    color: RED,
+                },
+                TerrainSpan {
+                    start: Point::new(20, 0),
+                    length: 10,
+                    color: BLUE,
+                },
+                TerrainSpan {
+                    start: Point::new(30, 0),
+                    length: 5,
+                    color: RED,
+                },
+                TerrainSpan {
+                    start: Point::new(60, 0),
+                    length: 4,
+                    color: RED,
+                },
+                TerrainSpan {
+                    start: Point::new(64, 0),
+                    length: 4,
+                    color: BLUE,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn multi_color_run_to_end_of_row() {
+        let mut bitmap = TerrainBitmap::new(Dimensions::new(70, 1), RED);
+        bitmap.fill_rect(Point::new(50, 0), Dimensions::new(16, 1), RED);
+        bitmap.fill_rect(Point::new(66, 0), Dimensions::new(4, 1), BLUE);
+
+        assert_eq!(
+            bitmap.row_spans(0),
+            vec![
+                TerrainSpan {
+                    start: Point::new(50, 0),
+                    length: 16,
+                    color: RED,
+                },
+                TerrainSpan {
+                    start: Point::new(66, 0),
+                    length: 4,
+                    color: BLUE,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rows_outside_bitmap_have_no_spans() {
+        let mut bitmap = TerrainBitmap::new(Dimensions::new(10, 1), RED);
+        bitmap.fill_rect(Point::new(0, 0), Dimensions::new(10, 1), RED);
+
+        assert!(bitmap.row_spans(-1).is_empty());
+        assert!(bitmap.row_spans(1).is_empty());
+    }
+
+    #[test]
+    fn more_than_256_colors_are_kept_apart() {
+        let mut bitmap = TerrainBitmap::new(Dimensions::new(300, 1), RED);
+        for x in 0..300 {
+            bitmap.set(
+                Point::new(x, 0),
+                Color::RGB((x % 256) as u8, (x / 256) as u8, 1),
+            );
+        }
+
+        assert_eq!(bitmap.palette().len(), 301);
+        assert_eq!(bitmap.color(Point::new(0, 0)), Some(Color::RGB(0, 0, 1)));
+        assert_eq!(bitmap.color(Point::new(299, 0)), Some(Color::RGB(43, 1, 1)));
+        assert_eq!(bitmap.row_spans(0).len(), 300);
+    }
+}
@@ -1,5 +1,9 @@
 mod game_object;
 mod movable;
+mod renderer;
+mod widget;
 
 pub use game_object::*;
 pub use movable::*;
+pub use renderer::*;
+pub use widget::*;
@@ -1,6 +1,8 @@
-use crate::structs::{Dimensions, GameObjectType, Pixel, Point, Sprite};
-use crate::util::has_collided;
-use std::collections::{HashMap, HashSet};
+use crate::structs::{
+    BoundingBox, Dimensions, Footprint, GameObjectType, Point, RenderLayer, Sprite, SweepHit,
+};
+use crate::traits::Renderer;
+use crate::util::sweep_collision;
 
 ///
 /// GameObject
@@ -11,19 +13,83 @@ pub trait GameObject {
     }
 
     fn game_object_type(&self) -> GameObjectType;
+    fn render_layer(&self) -> RenderLayer;
     fn id(&self) -> u32;
     fn origin(&self) -> Point;
     fn set_origin(&mut self, new_origin: Point);
     fn sprite(&self) -> &Sprite;
     fn sprite_dimensions(&self) -> Dimensions;
-    fn effective_points(&self) -> &HashSet<Point>;
-    fn effective_pixels(&self) -> &HashMap<Point, Pixel>;
+
+    /// The sprite placed at the object's origin. Built on demand, so moving an object does not
+    /// need to update anything else.
+    fn footprint(&self) -> Footprint<'_> {
+        Footprint::new(self.sprite(), self.origin())
+    }
+
+    /// Origin before the last move. Objects that never move have none, so collisions are only
+    /// checked at their current position.
+    fn prev_origin(&self) -> Option<Point> {
+        None
+    }
+
+    /// Draw order within the object's render layer; higher values are drawn on top
+    fn z_index(&self) -> i32 {
+        0
+    }
+
+    /// Multiplier (0.0 to 1.0) applied to the alpha of the sprite when it is drawn
+    fn opacity(&self) -> f32 {
+        1.0
+    }
+
+    /// Whether `point` (in world space) is one of the object's collision points
+    fn is_solid_at(&self, point: Point) -> bool {
+        self.footprint().is_solid(point)
+    }
+
+    /// Every collision point in world space, produced by offsetting the sprite's collision mask
+    fn solid_points(&self) -> Box<dyn Iterator<Item = Point> + '_> {
+        Box::new(self.footprint().collision_points())
+    }
+
+    /// Draws the object with its origin at `screen_origin`
+    fn draw(&self, renderer: &mut dyn Renderer, screen_origin: Point) {
+        renderer.draw_sprite(self.sprite(), screen_origin, self.opacity());
+    }
+
+    /// World-space box around the collision points, or `None` if the object cannot collide
+    fn collision_bounds(&self) -> Option<BoundingBox> {
+        self.footprint().collision_bounds()
+    }
+
+    /// World-space box around the collision points along the path moved since `prev_origin`
+    fn swept_collision_bounds(&self) -> Option<BoundingBox> {
+        let bounds = self.sprite().collision_points().bounding_box()?;
+        let from = self.prev_origin().unwrap_or(self.origin());
+
+        Some(
+            bounds
+                .translate(from)
+                .union(&bounds.translate(self.origin())),
+        )
+    }
+
+    /// First contact with `obstacle` along the path moved since `prev_origin`. Unlike checking
+    /// only the current position, this cannot skip over obstacles thinner than a step.
+    fn sweep_against(&self, obstacle: &dyn GameObject) -> Option<SweepHit> {
+        sweep_collision(
+            self.sprite().collision_points(),
+            self.prev_origin().unwrap_or(self.origin()),
+            self.origin(),
+            |point| obstacle.is_solid_at(point),
+        )
+    }
 
     fn identity(&self) -> (GameObjectType, u32) {
         (self.game_object_type(), self.id())
     }
 
     fn has_collided_with(&self, other: Box<&dyn GameObject>) -> bool {
-        has_collided(self.effective_points(), other.effective_points())
+        self.solid_points().any(|point| other.is_solid_at(point))
     }
 }
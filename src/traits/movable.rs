@@ -1,5 +1,6 @@
 use crate::structs::{Direction, Point, Rotation};
 use crate::traits::GameObject;
+use crate::util::resolve_sliding_movement;
 
 ///
 /// Movable
@@ -13,7 +14,6 @@ pub trait Movable: GameObject {
     fn enable_rotation(&mut self);
     fn change_rotation(&mut self, new_rotation: Rotation);
     fn speed(&self) -> u32;
-    fn prev_origin(&self) -> Option<Point>;
     fn set_prev_origin(&mut self, current_origin: Point);
 
     fn apply_movement(&mut self) {
@@ -55,4 +55,21 @@ pub trait Movable: GameObject {
     fn prev_origin_unchecked(&self) -> Point {
         self.prev_origin().expect("Expected prev_origin to exist")
     }
+
+    /// Origin the object should end up at after sliding along `obstacle` on the path moved since
+    /// `prev_origin`, or `None` if the path is clear
+    fn slide_against(&self, obstacle: &dyn GameObject) -> Option<Point> {
+        let from = self.prev_origin().unwrap_or(self.origin());
+        let resolved_origin = resolve_sliding_movement(
+            self.sprite().collision_points(),
+            from,
+            self.origin(),
+            |point| obstacle.is_solid_at(point),
+        );
+
+        match resolved_origin != self.origin() {
+            true => Some(resolved_origin),
+            false => None,
+        }
+    }
 }
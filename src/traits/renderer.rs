@@ -0,0 +1,16 @@
+use crate::structs::{Color, Dimensions, Point, Sprite};
+
+///
+/// Renderer
+///
+pub trait Renderer {
+    fn clear(&mut self, color: Color);
+    /// Blends the sprite over what has already been drawn. `opacity` (0.0 to 1.0) multiplies the
+    /// alpha of every pixel.
+    fn draw_sprite(&mut self, sprite: &Sprite, origin: Point, opacity: f32);
+    /// Blends a single pixel over what has already been drawn
+    fn draw_point(&mut self, point: Point, color: Color);
+    /// Blends a solid rectangle with its top-left corner at `origin`
+    fn fill_rect(&mut self, origin: Point, dimensions: Dimensions, color: Color);
+    fn present(&mut self);
+}
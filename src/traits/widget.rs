@@ -0,0 +1,30 @@
+use crate::structs::{Dimensions, PlayerInput, Point};
+use crate::traits::Renderer;
+use crate::ui::{UiTheme, WidgetEvent};
+
+///
+/// Widget
+///
+/// A single row of a `Menu`. The menu handles focus and layout; widgets only react to input
+/// while focused and draw themselves into the row they are given.
+pub trait Widget {
+    fn label(&self) -> &str;
+    /// Called only while the widget has focus. Returns an event when the input changed or
+    /// activated the widget.
+    fn handle_input(&mut self, input: PlayerInput) -> Option<WidgetEvent>;
+    /// `origin` is the top-left corner of the row
+    fn render(
+        &self,
+        renderer: &mut dyn Renderer,
+        theme: &UiTheme,
+        origin: Point,
+        row: Dimensions,
+        focused: bool,
+    );
+
+    /// Widgets that take typed text keep letter keys for themselves, so they are not used for
+    /// menu navigation while the widget has focus
+    fn captures_text(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,17 @@
+mod button;
+mod hud;
+mod list;
+mod menu;
+mod slider;
+mod text_field;
+mod ui_theme;
+mod widget_event;
+
+pub use button::*;
+pub use hud::*;
+pub use list::*;
+pub use menu::*;
+pub use slider::*;
+pub use text_field::*;
+pub use ui_theme::*;
+pub use widget_event::*;
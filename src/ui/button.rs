@@ -0,0 +1,54 @@
+use crate::render::TextAlign;
+use crate::structs::{Dimensions, Key, PlayerInput, Point};
+use crate::traits::{Renderer, Widget};
+use crate::ui::{UiTheme, WidgetEvent};
+
+///
+/// Button definition
+///
+pub struct Button {
+    label: String,
+}
+
+///
+/// Button implementation
+///
+impl Button {
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: label.to_owned(),
+        }
+    }
+}
+
+impl Widget for Button {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn handle_input(&mut self, input: PlayerInput) -> Option<WidgetEvent> {
+        match input {
+            PlayerInput::KeyDown(Key::Enter | Key::SpaceBar) => Some(WidgetEvent::Activated),
+            _ => None,
+        }
+    }
+
+    fn render(
+        &self,
+        renderer: &mut dyn Renderer,
+        theme: &UiTheme,
+        origin: Point,
+        row: Dimensions,
+        focused: bool,
+    ) {
+        theme.font.draw_text(
+            renderer,
+            &self.label,
+            Point::new(
+                origin.x + row.width as i32 / 2,
+                origin.y + theme.padding as i32,
+            ),
+            &theme.text_style(focused, TextAlign::Center),
+        );
+    }
+}
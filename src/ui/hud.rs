@@ -0,0 +1,155 @@
+use crate::render::{BitmapFont, TextAlign, TextStyle, DEFAULT_FONT_NAME};
+use crate::structs::{Color, Dimensions, Point};
+use crate::traits::Renderer;
+use crate::util::warn;
+
+// Distance of the HUD from the edges of the viewport
+const HUD_MARGIN: i32 = 8;
+const HUD_TEXT_SCALE: u32 = 2;
+const COOLDOWN_BAR_WIDTH: u32 = 60;
+
+const HUD_TEXT_COLOR: Color = Color::WHITE;
+const HUD_COIN_COLOR: Color = Color::RGB(255, 215, 0);
+const COOLDOWN_READY_COLOR: Color = Color::RGB(80, 220, 80);
+const COOLDOWN_CHARGING_COLOR: Color = Color::RGB(220, 140, 40);
+const COOLDOWN_BACKGROUND_COLOR: Color = Color::RGBA(255, 255, 255, 60);
+
+///
+/// HudStats definition
+///
+/// Snapshot of the values shown on the HUD for one frame
+#[derive(Debug, Copy, Clone)]
+pub struct HudStats {
+    pub coins: u32,
+    pub speed: u32,
+    // 0.0 right after firing, 1.0 once the next shot can be fired
+    pub fire_readiness: f32,
+    pub elapsed_frames: u64,
+    pub frame_rate: u32,
+}
+
+///
+/// Hud definition
+///
+/// Heads-up display drawn in screen space on top of the arena, so it does not move with the
+/// camera.
+pub struct Hud {
+    // Without a font the HUD draws nothing
+    font: Option<BitmapFont>,
+    visible: bool,
+}
+
+///
+/// Hud implementation
+///
+impl Hud {
+    pub fn new() -> Self {
+        let font = BitmapFont::new_from_file(DEFAULT_FONT_NAME)
+            .map_err(|err| warn(format!("Failed to load HUD font: {}", err)))
+            .ok();
+
+        Self {
+            font,
+            visible: true,
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn render(&self, renderer: &mut dyn Renderer, viewport: Dimensions, stats: &HudStats) {
+        let font = match (&self.font, self.visible) {
+            (Some(font), true) => font,
+            _ => return,
+        };
+
+        let style = TextStyle {
+            color: HUD_TEXT_COLOR,
+            align: TextAlign::Left,
+            scale: HUD_TEXT_SCALE,
+        };
+        let line_height = font.measure("0", HUD_TEXT_SCALE).height as i32 + HUD_MARGIN / 2;
+
+        // Top left: score and player stats
+        let mut position = Point::new(HUD_MARGIN, HUD_MARGIN);
+        font.draw_text(
+            renderer,
+            &format!("COINS {}", stats.coins),
+            position,
+            &TextStyle {
+                color: HUD_COIN_COLOR,
+                ..style
+            },
+        );
+
+        position.y += line_height;
+        font.draw_text(
+            renderer,
+            &format!("SPEED {}", stats.speed),
+            position,
+            &style,
+        );
+
+        position.y += line_height;
+        font.draw_text(renderer, "FIRE", position, &style);
+        let label_width = font.measure("FIRE ", HUD_TEXT_SCALE).width as i32;
+        self.render_cooldown_bar(
+            renderer,
+            Point::new(position.x + label_width, position.y),
+            Dimensions::new(COOLDOWN_BAR_WIDTH, font.measure("0", HUD_TEXT_SCALE).height),
+            stats.fire_readiness,
+        );
+
+        // Top right: match timer
+        font.draw_text(
+            renderer,
+            &format_match_time(stats.elapsed_frames, stats.frame_rate),
+            Point::new(viewport.width as i32 - HUD_MARGIN, HUD_MARGIN),
+            &TextStyle {
+                align: TextAlign::Right,
+                ..style
+            },
+        );
+    }
+
+    fn render_cooldown_bar(
+        &self,
+        renderer: &mut dyn Renderer,
+        origin: Point,
+        dimensions: Dimensions,
+        readiness: f32,
+    ) {
+        let readiness = readiness.clamp(0.0, 1.0);
+        let fill_color = match readiness >= 1.0 {
+            true => COOLDOWN_READY_COLOR,
+            false => COOLDOWN_CHARGING_COLOR,
+        };
+
+        renderer.fill_rect(origin, dimensions, COOLDOWN_BACKGROUND_COLOR);
+        renderer.fill_rect(
+            origin,
+            Dimensions::new(
+                (dimensions.width as f32 * readiness).round() as u32,
+                dimensions.height,
+            ),
+            fill_color,
+        );
+    }
+}
+
+impl Default for Hud {
+    fn default() -> Self {
+        Hud::new()
+    }
+}
+
+/// `MM:SS` since the start of the match
+fn format_match_time(elapsed_frames: u64, frame_rate: u32) -> String {
+    let seconds = elapsed_frames / frame_rate.max(1) as u64;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
@@ -0,0 +1,95 @@
+use crate::render::TextAlign;
+use crate::structs::{Dimensions, Key, PlayerInput, Point};
+use crate::traits::{Renderer, Widget};
+use crate::ui::{UiTheme, WidgetEvent};
+
+///
+/// List definition
+///
+/// A choice between a list of options, cycled with left and right
+pub struct List {
+    label: String,
+    options: Vec<String>,
+    selected: usize,
+}
+
+///
+/// List implementation
+///
+impl List {
+    pub fn new(label: &str, options: Vec<String>) -> Self {
+        Self {
+            label: label.to_owned(),
+            options,
+            selected: 0,
+        }
+    }
+
+    pub fn with_selected(mut self, index: usize) -> Self {
+        self.select(index);
+        self
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_option(&self) -> Option<&str> {
+        self.options.get(self.selected).map(String::as_str)
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(self.options.len().saturating_sub(1));
+    }
+
+    fn cycle(&mut self, step: isize) -> Option<WidgetEvent> {
+        if self.options.is_empty() {
+            return None;
+        }
+
+        let count = self.options.len() as isize;
+        self.selected = (self.selected as isize + step).rem_euclid(count) as usize;
+        Some(WidgetEvent::Selected(self.selected))
+    }
+}
+
+impl Widget for List {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn handle_input(&mut self, input: PlayerInput) -> Option<WidgetEvent> {
+        match input {
+            PlayerInput::KeyDown(Key::Left | Key::A | Key::J) => self.cycle(-1),
+            PlayerInput::KeyDown(Key::Right | Key::D | Key::L | Key::Enter | Key::SpaceBar) => {
+                self.cycle(1)
+            }
+            _ => None,
+        }
+    }
+
+    fn render(
+        &self,
+        renderer: &mut dyn Renderer,
+        theme: &UiTheme,
+        origin: Point,
+        row: Dimensions,
+        focused: bool,
+    ) {
+        let text_y = origin.y + theme.padding as i32;
+        theme.font.draw_text(
+            renderer,
+            &self.label,
+            Point::new(origin.x + theme.padding as i32, text_y),
+            &theme.text_style(focused, TextAlign::Left),
+        );
+
+        let option = self.selected_option().unwrap_or("");
+        theme.font.draw_text(
+            renderer,
+            &format!("< {} >", option),
+            Point::new(origin.x + row.width as i32 - theme.padding as i32, text_y),
+            &theme.text_style(focused, TextAlign::Right),
+        );
+    }
+}
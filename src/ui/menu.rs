@@ -0,0 +1,151 @@
+use crate::render::{TextAlign, TextStyle};
+use crate::structs::{Dimensions, Key, PlayerInput, Point};
+use crate::traits::{Renderer, Widget};
+use crate::ui::{MenuEvent, UiTheme};
+
+// Share of the viewport width taken up by the menu
+const MENU_WIDTH_RATIO: f32 = 0.6;
+
+///
+/// Menu definition
+///
+/// A titled, vertical stack of widgets centered in the viewport. Up and down move the focus
+/// (wrapping around); every other input goes to the focused widget.
+pub struct Menu {
+    title: String,
+    items: Vec<MenuItem>,
+    focused: usize,
+}
+
+struct MenuItem {
+    // Identifies the widget in `MenuEvent`s
+    id: String,
+    widget: Box<dyn Widget>,
+}
+
+///
+/// Menu implementation
+///
+impl Menu {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_owned(),
+            items: Vec::new(),
+            focused: 0,
+        }
+    }
+
+    pub fn with_widget(mut self, id: &str, widget: impl Widget + 'static) -> Self {
+        self.add_widget(id, widget);
+        self
+    }
+
+    pub fn add_widget(&mut self, id: &str, widget: impl Widget + 'static) {
+        self.items.push(MenuItem {
+            id: id.to_owned(),
+            widget: Box::new(widget),
+        });
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.to_owned();
+    }
+
+    /// Id of the widget that has focus
+    pub fn focused(&self) -> Option<&str> {
+        self.items.get(self.focused).map(|item| item.id.as_str())
+    }
+
+    pub fn focus(&mut self, id: &str) {
+        if let Some(index) = self.items.iter().position(|item| item.id == id) {
+            self.focused = index;
+        }
+    }
+
+    /// Whether the focused widget takes typed text, so printable keys should arrive as
+    /// `PlayerInput::TextInput`
+    pub fn captures_text(&self) -> bool {
+        self.items
+            .get(self.focused)
+            .map(|item| item.widget.captures_text())
+            .unwrap_or(false)
+    }
+
+    pub fn handle_input(&mut self, input: PlayerInput) -> Option<MenuEvent> {
+        let captures_text = self.captures_text();
+
+        match input {
+            PlayerInput::KeyDown(Key::Up) => self.move_focus(-1),
+            PlayerInput::KeyDown(Key::Down) => self.move_focus(1),
+            PlayerInput::KeyDown(Key::W | Key::I) if !captures_text => self.move_focus(-1),
+            PlayerInput::KeyDown(Key::S | Key::K) if !captures_text => self.move_focus(1),
+            _ => {
+                let item = self.items.get_mut(self.focused)?;
+                return item.widget.handle_input(input).map(|event| MenuEvent {
+                    id: item.id.clone(),
+                    event,
+                });
+            }
+        }
+
+        None
+    }
+
+    pub fn render(&self, renderer: &mut dyn Renderer, theme: &UiTheme, viewport: Dimensions) {
+        let padding = theme.padding as i32;
+        let row_height = theme.row_height();
+        let width = (viewport.width as f32 * MENU_WIDTH_RATIO) as u32;
+        let title_height = theme.font.measure(&self.title, theme.title_scale).height;
+        let height = title_height + theme.padding * 3 + row_height * self.items.len() as u32;
+
+        let origin = Point::new(
+            (viewport.width as i32 - width as i32) / 2,
+            (viewport.height as i32 - height as i32) / 2,
+        );
+
+        renderer.fill_rect(
+            origin,
+            Dimensions::new(width, height),
+            theme.background_color,
+        );
+        theme.font.draw_text(
+            renderer,
+            &self.title,
+            Point::new(origin.x + width as i32 / 2, origin.y + padding),
+            &TextStyle {
+                color: theme.focused_text_color,
+                align: TextAlign::Center,
+                scale: theme.title_scale,
+            },
+        );
+
+        let row = Dimensions::new(width.saturating_sub(theme.padding * 2), row_height);
+        let mut row_origin = Point::new(
+            origin.x + padding,
+            origin.y + title_height as i32 + padding * 2,
+        );
+        for (index, item) in self.items.iter().enumerate() {
+            let focused = index == self.focused;
+            if focused {
+                renderer.fill_rect(row_origin, row, theme.focus_background_color);
+            }
+
+            item.widget
+                .render(renderer, theme, row_origin, row, focused);
+            row_origin.y += row_height as i32;
+        }
+    }
+
+    fn move_focus(&mut self, step: isize) {
+        if self.items.is_empty() {
+            return;
+        }
+
+        let count = self.items.len() as isize;
+        self.focused = (self.focused as isize + step).rem_euclid(count) as usize;
+    }
+}
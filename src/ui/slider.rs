@@ -0,0 +1,115 @@
+use crate::render::TextAlign;
+use crate::structs::{Dimensions, Key, PlayerInput, Point};
+use crate::traits::{Renderer, Widget};
+use crate::ui::{UiTheme, WidgetEvent};
+
+// Share of the row taken up by the slider track, to the right of the label
+const TRACK_WIDTH_RATIO: f32 = 0.4;
+
+///
+/// Slider definition
+///
+pub struct Slider {
+    label: String,
+    value: i32,
+    min: i32,
+    max: i32,
+    step: i32,
+}
+
+///
+/// Slider implementation
+///
+impl Slider {
+    pub fn new(label: &str, min: i32, max: i32, step: i32) -> Self {
+        Self {
+            label: label.to_owned(),
+            value: min,
+            min,
+            max: max.max(min),
+            step: step.max(1),
+        }
+    }
+
+    pub fn with_value(mut self, value: i32) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    pub fn set_value(&mut self, value: i32) {
+        self.value = value.clamp(self.min, self.max);
+    }
+
+    fn nudge(&mut self, step: i32) -> Option<WidgetEvent> {
+        let previous = self.value;
+        self.set_value(self.value + step);
+
+        match self.value != previous {
+            true => Some(WidgetEvent::ValueChanged(self.value)),
+            false => None,
+        }
+    }
+}
+
+impl Widget for Slider {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn handle_input(&mut self, input: PlayerInput) -> Option<WidgetEvent> {
+        match input {
+            PlayerInput::KeyDown(Key::Left | Key::A | Key::J) => self.nudge(-self.step),
+            PlayerInput::KeyDown(Key::Right | Key::D | Key::L) => self.nudge(self.step),
+            _ => None,
+        }
+    }
+
+    fn render(
+        &self,
+        renderer: &mut dyn Renderer,
+        theme: &UiTheme,
+        origin: Point,
+        row: Dimensions,
+        focused: bool,
+    ) {
+        let padding = theme.padding as i32;
+        let text_y = origin.y + padding;
+        theme.font.draw_text(
+            renderer,
+            &self.label,
+            Point::new(origin.x + padding, text_y),
+            &theme.text_style(focused, TextAlign::Left),
+        );
+
+        // Value is drawn to the left of the track
+        let track_width = (row.width as f32 * TRACK_WIDTH_RATIO) as u32;
+        let track_height = row.height.saturating_sub(theme.padding * 2);
+        let track_origin = Point::new(
+            origin.x + row.width as i32 - padding - track_width as i32,
+            text_y,
+        );
+        theme.font.draw_text(
+            renderer,
+            &self.value.to_string(),
+            Point::new(track_origin.x - padding, text_y),
+            &theme.text_style(focused, TextAlign::Right),
+        );
+
+        let range = (self.max - self.min).max(1) as f32;
+        let filled = ((self.value - self.min) as f32 / range * track_width as f32).round() as u32;
+        renderer.fill_rect(
+            track_origin,
+            Dimensions::new(track_width, track_height),
+            theme.focus_background_color,
+        );
+        renderer.fill_rect(
+            track_origin,
+            Dimensions::new(filled, track_height),
+            theme.accent_color,
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::render::TextAlign;
+use crate::structs::{Dimensions, Key, PlayerInput, Point};
+use crate::traits::{Renderer, Widget};
+use crate::ui::{UiTheme, WidgetEvent};
+
+///
+/// TextField definition
+///
+/// Single line of text typed by the player, e.g. a name. Receives `PlayerInput::TextInput`.
+pub struct TextField {
+    label: String,
+    text: String,
+    // In characters
+    max_length: usize,
+}
+
+///
+/// TextField implementation
+///
+impl TextField {
+    pub fn new(label: &str, max_length: usize) -> Self {
+        Self {
+            label: label.to_owned(),
+            text: String::new(),
+            max_length,
+        }
+    }
+
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = text.chars().take(self.max_length).collect();
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Widget for TextField {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn handle_input(&mut self, input: PlayerInput) -> Option<WidgetEvent> {
+        match input {
+            PlayerInput::TextInput(character)
+                if !character.is_control() && self.text.chars().count() < self.max_length =>
+            {
+                self.text.push(character);
+                Some(WidgetEvent::TextChanged(self.text.clone()))
+            }
+            PlayerInput::KeyDown(Key::Backspace) => self
+                .text
+                .pop()
+                .map(|_| WidgetEvent::TextChanged(self.text.clone())),
+            PlayerInput::KeyDown(Key::Enter) => Some(WidgetEvent::Submitted(self.text.clone())),
+            _ => None,
+        }
+    }
+
+    fn render(
+        &self,
+        renderer: &mut dyn Renderer,
+        theme: &UiTheme,
+        origin: Point,
+        row: Dimensions,
+        focused: bool,
+    ) {
+        let padding = theme.padding as i32;
+        let text_y = origin.y + padding;
+        theme.font.draw_text(
+            renderer,
+            &self.label,
+            Point::new(origin.x + padding, text_y),
+            &theme.text_style(focused, TextAlign::Left),
+        );
+
+        let text = match focused {
+            true => format!("{}_", self.text),
+            false => self.text.clone(),
+        };
+        theme.font.draw_text(
+            renderer,
+            &text,
+            Point::new(origin.x + row.width as i32 - padding, text_y),
+            &theme.text_style(focused, TextAlign::Right),
+        );
+    }
+
+    fn captures_text(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,60 @@
+use crate::render::{BitmapFont, TextAlign, TextStyle, DEFAULT_FONT_NAME};
+use crate::structs::{Color, SpriteError};
+
+///
+/// UiTheme definition
+///
+/// Font, sizes and colors shared by every widget
+pub struct UiTheme {
+    pub font: BitmapFont,
+    pub text_scale: u32,
+    pub title_scale: u32,
+    // Space around the text of each row
+    pub padding: u32,
+    pub text_color: Color,
+    pub focused_text_color: Color,
+    pub focus_background_color: Color,
+    // Filled part of sliders
+    pub accent_color: Color,
+    pub background_color: Color,
+}
+
+///
+/// UiTheme implementation
+///
+impl UiTheme {
+    pub fn new() -> Result<Self, SpriteError> {
+        Ok(UiTheme::with_font(BitmapFont::new_from_file(
+            DEFAULT_FONT_NAME,
+        )?))
+    }
+
+    pub fn with_font(font: BitmapFont) -> Self {
+        Self {
+            font,
+            text_scale: 2,
+            title_scale: 4,
+            padding: 6,
+            text_color: Color::RGB(200, 200, 200),
+            focused_text_color: Color::WHITE,
+            focus_background_color: Color::RGBA(255, 255, 255, 40),
+            accent_color: Color::RGB(255, 215, 0),
+            background_color: Color::RGBA(0, 0, 0, 200),
+        }
+    }
+
+    pub fn row_height(&self) -> u32 {
+        self.font.measure("0", self.text_scale).height + self.padding * 2
+    }
+
+    pub fn text_style(&self, focused: bool, align: TextAlign) -> TextStyle {
+        TextStyle {
+            color: match focused {
+                true => self.focused_text_color,
+                false => self.text_color,
+            },
+            align,
+            scale: self.text_scale,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+///
+/// WidgetEvent definition
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WidgetEvent {
+    // A button was pressed
+    Activated,
+    // A list moved to the option at this index
+    Selected(usize),
+    // A slider moved to this value
+    ValueChanged(i32),
+    // The text of a text field was edited
+    TextChanged(String),
+    // Enter was pressed in a text field
+    Submitted(String),
+}
+
+///
+/// MenuEvent definition
+///
+/// A `WidgetEvent`, tagged with the id the widget was added to the menu with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuEvent {
+    pub id: String,
+    pub event: WidgetEvent,
+}
@@ -1,5 +1,11 @@
+mod asset_lint;
+mod assets;
+mod diagnostics;
 mod performance_tracking;
-mod util;
+mod sweep;
 
+pub use asset_lint::*;
+pub use assets::*;
+pub use diagnostics::*;
 pub use performance_tracking::*;
-pub use util::*;
+pub use sweep::*;
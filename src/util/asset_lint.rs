@@ -0,0 +1,367 @@
+use crate::structs::{Dimensions, HitboxMetadata, Point, SpriteError, SpriteMetadata};
+use crate::util::embedded_file;
+use image::{DynamicImage, GenericImageView};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+///
+/// AssetIssue definition
+///
+#[derive(Debug)]
+pub enum AssetIssue {
+    // The directory to lint does not exist
+    MissingAssetRoot(PathBuf),
+    // A file could not be read, parsed or decoded
+    Unreadable(SpriteError),
+    // The metadata dimensions do not match the PNG
+    SizeMismatch {
+        path: PathBuf,
+        metadata: Dimensions,
+        image: Dimensions,
+    },
+    // The origin lies outside of the image
+    OriginOutOfBounds {
+        path: PathBuf,
+        origin: Point,
+        image: Dimensions,
+    },
+    // Every pixel of the image is transparent
+    FullyTransparent(PathBuf),
+    // A PNG without a matching JSON metadata file
+    OrphanedImage(PathBuf),
+    // A JSON metadata file without a matching PNG
+    OrphanedMetadata(PathBuf),
+    // A font's glyph grid extends past the edge of the image
+    GlyphsOutOfBounds {
+        path: PathBuf,
+        grid: Dimensions,
+        image: Dimensions,
+    },
+    // An embedded sprite's hitbox mask is not embedded, so the fallback sprite cannot load
+    MaskNotEmbedded(PathBuf),
+}
+
+impl std::fmt::Display for AssetIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AssetIssue::MissingAssetRoot(path) => {
+                write!(f, "asset directory not found: {}", path.display())
+            }
+            AssetIssue::Unreadable(err) => write!(f, "{}", err),
+            AssetIssue::SizeMismatch {
+                path,
+                metadata,
+                image,
+            } => write!(
+                f,
+                "{}: metadata dimensions {}x{} do not match image dimensions {}x{}",
+                path.display(),
+                metadata.width,
+                metadata.height,
+                image.width,
+                image.height
+            ),
+            AssetIssue::OriginOutOfBounds {
+                path,
+                origin,
+                image,
+            } => write!(
+                f,
+                "{}: origin ({}, {}) lies outside the {}x{} image",
+                path.display(),
+                origin.x,
+                origin.y,
+                image.width,
+                image.height
+            ),
+            AssetIssue::FullyTransparent(path) => {
+                write!(f, "{}: image is fully transparent", path.display())
+            }
+            AssetIssue::OrphanedImage(path) => {
+                write!(
+                    f,
+                    "{}: image has no matching .json metadata",
+                    path.display()
+                )
+            }
+            AssetIssue::OrphanedMetadata(path) => {
+                write!(f, "{}: metadata has no matching .png image", path.display())
+            }
+            AssetIssue::GlyphsOutOfBounds { path, grid, image } => write!(
+                f,
+                "{}: {}x{} glyph grid does not fit the {}x{} image",
+                path.display(),
+                grid.width,
+                grid.height,
+                image.width,
+                image.height
+            ),
+            AssetIssue::MaskNotEmbedded(path) => write!(
+                f,
+                "{}: hitbox mask of an embedded sprite is not embedded",
+                path.display()
+            ),
+        }
+    }
+}
+
+/// Checks sprite metadata against the image it describes.
+pub fn validate_sprite(
+    metadata_path: &Path,
+    metadata: &SpriteMetadata,
+    image: &DynamicImage,
+) -> Vec<AssetIssue> {
+    let mut issues = Vec::new();
+    let image_dimensions = Dimensions::new(image.width(), image.height());
+
+    if metadata.dimensions != image_dimensions {
+        issues.push(AssetIssue::SizeMismatch {
+            path: metadata_path.to_path_buf(),
+            metadata: metadata.dimensions,
+            image: image_dimensions,
+        });
+    }
+
+    let origin = metadata.origin;
+    if origin.x < 0
+        || origin.y < 0
+        || origin.x as u32 >= image_dimensions.width
+        || origin.y as u32 >= image_dimensions.height
+    {
+        issues.push(AssetIssue::OriginOutOfBounds {
+            path: metadata_path.to_path_buf(),
+            origin,
+            image: image_dimensions,
+        });
+    }
+
+    if let Some(glyphs) = &metadata.glyphs {
+        let grid = glyphs.grid_dimensions();
+        if grid.width > image_dimensions.width || grid.height > image_dimensions.height {
+            issues.push(AssetIssue::GlyphsOutOfBounds {
+                path: metadata_path.to_path_buf(),
+                grid,
+                image: image_dimensions,
+            });
+        }
+    }
+
+    if image.pixels().all(|(_x, _y, rgba)| rgba[3] == 0) {
+        issues.push(AssetIssue::FullyTransparent(
+            metadata_path.with_extension("png"),
+        ));
+    }
+
+    issues
+}
+
+/// Walks `dir` (recursively) and reports every inconsistency between sprite metadata and images.
+pub fn lint_sprite_dir(dir: &Path) -> Vec<AssetIssue> {
+    // Sprite files keyed by their path without extension: (metadata, image)
+    let mut sprite_files: BTreeMap<PathBuf, (Option<PathBuf>, Option<PathBuf>)> = BTreeMap::new();
+    let mut issues = Vec::new();
+
+    if !dir.is_dir() {
+        return vec![AssetIssue::MissingAssetRoot(dir.to_path_buf())];
+    }
+
+    let mut dirs_to_visit = vec![dir.to_path_buf()];
+    while let Some(current_dir) = dirs_to_visit.pop() {
+        let entries = match fs::read_dir(&current_dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                issues.push(AssetIssue::Unreadable(SpriteError::from_io(
+                    current_dir,
+                    err,
+                )));
+                continue;
+            }
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs_to_visit.push(path);
+                continue;
+            }
+
+            let files = sprite_files.entry(path.with_extension("")).or_default();
+            match path.extension().and_then(|ext| ext.to_str()) {
+                Some("json") => files.0 = Some(path),
+                Some("png") => files.1 = Some(path),
+                _ => {}
+            }
+        }
+    }
+
+    // Hitbox masks are PNGs without metadata of their own
+    let hitbox_masks: HashSet<PathBuf> = sprite_files
+        .values()
+        .filter_map(|(metadata_path, _)| metadata_path.as_ref())
+        .filter_map(|metadata_path| match read_metadata(metadata_path) {
+            Ok(SpriteMetadata {
+                hitbox: Some(HitboxMetadata::Mask(mask_name)),
+                ..
+            }) => Some(metadata_path.with_file_name(mask_name)),
+            _ => None,
+        })
+        .collect();
+
+    for (stem, (metadata_path, image_path)) in sprite_files {
+        match (metadata_path, image_path) {
+            (Some(metadata_path), Some(image_path)) => {
+                issues.extend(lint_sprite_files(&metadata_path, &image_path))
+            }
+            (None, Some(image_path)) => {
+                if !hitbox_masks.contains(&stem) {
+                    issues.push(AssetIssue::OrphanedImage(image_path))
+                }
+            }
+            (Some(metadata_path), None) => issues.push(AssetIssue::OrphanedMetadata(metadata_path)),
+            (None, None) => {}
+        }
+    }
+
+    issues
+}
+
+fn lint_sprite_files(metadata_path: &Path, image_path: &Path) -> Vec<AssetIssue> {
+    match (read_metadata(metadata_path), read_image(image_path)) {
+        (Ok(metadata), Ok(image)) => {
+            let mut issues = validate_sprite(metadata_path, &metadata, &image);
+            if let Some(HitboxMetadata::Mask(mask_name)) = &metadata.hitbox {
+                let mask_file_name = format!("{}.png", mask_name);
+                let mask_path = metadata_path.with_file_name(&mask_file_name);
+                if is_embedded(metadata_path) && embedded_file(&mask_file_name).is_none() {
+                    issues.push(AssetIssue::MaskNotEmbedded(mask_path.clone()));
+                }
+                match read_image(&mask_path) {
+                    Ok(mask) => {
+                        let mask_dimensions = Dimensions::new(mask.width(), mask.height());
+                        if mask_dimensions != metadata.dimensions {
+                            issues.push(AssetIssue::SizeMismatch {
+                                path: mask_path,
+                                metadata: metadata.dimensions,
+                                image: mask_dimensions,
+                            });
+                        }
+                    }
+                    Err(err) => issues.push(AssetIssue::Unreadable(err)),
+                }
+            }
+            issues
+        }
+        (metadata, image) => [metadata.err(), image.err()]
+            .into_iter()
+            .flatten()
+            .map(AssetIssue::Unreadable)
+            .collect(),
+    }
+}
+
+/// Whether the sprite is compiled into the binary (only with the `embedded-assets` feature)
+fn is_embedded(metadata_path: &Path) -> bool {
+    metadata_path
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .is_some_and(|file_name| embedded_file(file_name).is_some())
+}
+
+fn read_metadata(metadata_path: &Path) -> Result<SpriteMetadata, SpriteError> {
+    let contents = fs::read_to_string(metadata_path)
+        .map_err(|err| SpriteError::from_io(metadata_path.to_path_buf(), err))?;
+
+    serde_json::from_str(&contents).map_err(|err| SpriteError::InvalidMetadata {
+        path: metadata_path.to_path_buf(),
+        source: err,
+    })
+}
+
+fn read_image(image_path: &Path) -> Result<DynamicImage, SpriteError> {
+    let contents =
+        fs::read(image_path).map_err(|err| SpriteError::from_io(image_path.to_path_buf(), err))?;
+
+    image::load_from_memory(&contents).map_err(|err| SpriteError::ImageDecode {
+        path: image_path.to_path_buf(),
+        source: err,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn missing_asset_root_is_reported_as_such() {
+        let dir =
+            std::env::temp_dir().join(format!("poly_battle_lint_missing_{}", std::process::id()));
+
+        let issues = lint_sprite_dir(&dir);
+
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(&issues[0], AssetIssue::MissingAssetRoot(path) if *path == dir));
+    }
+
+    #[test]
+    fn size_mismatch_and_orphaned_image_are_reported() {
+        let dir =
+            std::env::temp_dir().join(format!("poly_battle_lint_sprites_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("wall.json"),
+            r#"{ "dimensions": { "width": 10, "height": 10 }, "origin": { "x": 4, "y": 4 } }"#,
+        )
+        .unwrap();
+        RgbaImage::from_pixel(8, 8, Rgba([255, 255, 255, 255]))
+            .save(dir.join("wall.png"))
+            .unwrap();
+        RgbaImage::from_pixel(4, 4, Rgba([255, 255, 255, 255]))
+            .save(dir.join("stray.png"))
+            .unwrap();
+
+        let issues = lint_sprite_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().any(|issue| matches!(
+            issue,
+            AssetIssue::SizeMismatch { path, metadata, image }
+                if *path == dir.join("wall.json")
+                    && *metadata == Dimensions::new(10, 10)
+                    && *image == Dimensions::new(8, 8)
+        )));
+        assert!(issues.iter().any(
+            |issue| matches!(issue, AssetIssue::OrphanedImage(path) if *path == dir.join("stray.png"))
+        ));
+    }
+
+    #[cfg(feature = "embedded-assets")]
+    #[test]
+    fn mask_of_embedded_sprite_must_be_embedded() {
+        let dir =
+            std::env::temp_dir().join(format!("poly_battle_lint_masks_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        // Named like an embedded sprite, with a mask that is not embedded
+        fs::write(
+            dir.join("coin_sprite.json"),
+            r#"{ "dimensions": { "width": 10, "height": 10 }, "origin": { "x": 4, "y": 4 }, "hitbox": { "mask": "coin_mask" } }"#,
+        )
+        .unwrap();
+        RgbaImage::from_pixel(10, 10, Rgba([255, 255, 255, 255]))
+            .save(dir.join("coin_sprite.png"))
+            .unwrap();
+        RgbaImage::from_pixel(10, 10, Rgba([255, 255, 255, 255]))
+            .save(dir.join("coin_mask.png"))
+            .unwrap();
+
+        let issues = lint_sprite_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(issues.len(), 1);
+        assert!(
+            matches!(&issues[0], AssetIssue::MaskNotEmbedded(path) if *path == dir.join("coin_mask.png"))
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+pub const ASSET_ROOT_ENV_VAR: &str = "POLY_BATTLE_ASSETS";
+pub const DEFAULT_ASSET_ROOT: &str = "resources";
+pub const SPRITE_SUBDIR: &str = "sprites";
+// Pseudo-directory reported in errors for assets compiled into the binary
+pub const EMBEDDED_ASSET_DIR: &str = "<embedded>";
+
+// How many directories above the executable to search for an asset root. Three levels covers
+// `target/<profile>/poly_battle` when running from a checkout.
+const MAX_EXE_SEARCH_DEPTH: usize = 3;
+
+static ASSET_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// Explicitly sets the asset root (e.g. from a CLI argument). Must be called before any asset
+/// is loaded; returns an error if the asset root has already been resolved.
+pub fn set_asset_root(path: impl Into<PathBuf>) -> Result<(), String> {
+    ASSET_ROOT
+        .set(path.into())
+        .map_err(|_| "Asset root has already been set".to_string())
+}
+
+/// Directory that all assets are loaded from.
+///
+/// Resolved once, in order of precedence: `set_asset_root`, the `POLY_BATTLE_ASSETS`
+/// environment variable, a `resources` directory next to (or above) the executable, and
+/// finally `resources` relative to the working directory.
+pub fn asset_root() -> &'static Path {
+    ASSET_ROOT.get_or_init(resolve_asset_root)
+}
+
+pub fn sprite_dir() -> PathBuf {
+    asset_root().join(SPRITE_SUBDIR)
+}
+
+fn resolve_asset_root() -> PathBuf {
+    if let Some(env_root) = std::env::var_os(ASSET_ROOT_ENV_VAR) {
+        return PathBuf::from(env_root);
+    }
+
+    if let Some(exe_root) = find_asset_root_near_executable() {
+        return exe_root;
+    }
+
+    PathBuf::from(DEFAULT_ASSET_ROOT)
+}
+
+fn find_asset_root_near_executable() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+
+    exe.ancestors()
+        .skip(1)
+        .take(MAX_EXE_SEARCH_DEPTH + 1)
+        .map(|dir| dir.join(DEFAULT_ASSET_ROOT))
+        .find(|candidate| candidate.join(SPRITE_SUBDIR).is_dir())
+}
+
+/// Contents of an asset file (e.g. `coin_sprite.png`) compiled into the binary, if any.
+#[cfg(feature = "embedded-assets")]
+pub fn embedded_file(file_name: &str) -> Option<&'static [u8]> {
+    macro_rules! embed_sprite_files {
+        ($($name:literal),* $(,)?) => {
+            &[$(
+                (
+                    concat!($name, ".json"),
+                    include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/resources/sprites/", $name, ".json")).as_slice(),
+                ),
+                (
+                    concat!($name, ".png"),
+                    include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/resources/sprites/", $name, ".png")).as_slice(),
+                ),
+            )*]
+        };
+    }
+    macro_rules! embed_image_files {
+        ($($name:literal),* $(,)?) => {
+            &[$(
+                (
+                    concat!($name, ".png"),
+                    include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/resources/sprites/", $name, ".png")).as_slice(),
+                ),
+            )*]
+        };
+    }
+
+    const EMBEDDED_FILES: &[(&str, &[u8])] = embed_sprite_files!(
+        "coin_sprite",
+        "font",
+        "player_sprite_2",
+        "projectile_sprite"
+    );
+    // Hitbox masks of the sprites above, which have no metadata of their own. `lint-assets`
+    // reports masks that are missing here.
+    const EMBEDDED_MASKS: &[(&str, &[u8])] = embed_image_files!();
+
+    EMBEDDED_FILES
+        .iter()
+        .chain(EMBEDDED_MASKS)
+        .find(|(name, _)| *name == file_name)
+        .map(|(_, contents)| *contents)
+}
+
+#[cfg(not(feature = "embedded-assets"))]
+pub fn embedded_file(_file_name: &str) -> Option<&'static [u8]> {
+    None
+}
@@ -0,0 +1,17 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static WARNINGS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turns warnings on or off, e.g. off while the terminal renderer owns the screen and anything
+/// written to stderr would draw over the arena
+pub fn set_warnings_enabled(enabled: bool) {
+    WARNINGS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Prints a non-fatal problem (e.g. a missing asset) to stderr, unless warnings are turned off
+pub fn warn(message: impl Display) {
+    if WARNINGS_ENABLED.load(Ordering::Relaxed) {
+        eprintln!("{}", message);
+    }
+}
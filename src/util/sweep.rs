@@ -0,0 +1,129 @@
+use crate::structs::{PixelMask, Point, SweepHit};
+
+/// Origins passed through when moving from `from` to `to` one pixel at a time, excluding `from`.
+/// Yields only `to` when the two are equal.
+pub fn swept_origins(from: Point, to: Point) -> impl Iterator<Item = Point> {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let steps = dx.abs().max(dy.abs());
+
+    (steps.min(1)..=steps).map(move |step| match steps {
+        0 => to,
+        _ => Point::new(
+            from.x + (dx as f32 * step as f32 / steps as f32).round() as i32,
+            from.y + (dy as f32 * step as f32 / steps as f32).round() as i32,
+        ),
+    })
+}
+
+/// Moves `collision_points` (relative to the object's origin) along the path from `from` to `to`
+/// and returns the first position at which they touch a point for which `is_obstacle` holds.
+pub fn sweep_collision(
+    collision_points: &PixelMask,
+    from: Point,
+    to: Point,
+    is_obstacle: impl Fn(Point) -> bool,
+) -> Option<SweepHit> {
+    let mut last_free_origin = from;
+
+    for origin in swept_origins(from, to) {
+        let contact_point = collision_points
+            .points()
+            .map(|point| Point::new(origin.x + point.x, origin.y + point.y))
+            .find(|point| is_obstacle(*point));
+
+        if let Some(contact_point) = contact_point {
+            return Some(SweepHit {
+                origin,
+                last_free_origin,
+                contact_point,
+            });
+        }
+        last_free_origin = origin;
+    }
+
+    None
+}
+
+/// Where an object moving from `from` to `to` ends up when it slides along obstacles: it moves
+/// until the first contact, then spends the rest of the movement on each axis on its own, so
+/// only the blocked axis is cancelled. Returns `to` if nothing is in the way.
+pub fn resolve_sliding_movement(
+    collision_points: &PixelMask,
+    from: Point,
+    to: Point,
+    is_obstacle: impl Fn(Point) -> bool,
+) -> Point {
+    let first_hit = match sweep_collision(collision_points, from, to, &is_obstacle) {
+        Some(hit) => hit,
+        None => return to,
+    };
+
+    let move_until_blocked = |from: Point, to: Point| -> Point {
+        if from == to {
+            return from;
+        }
+
+        sweep_collision(collision_points, from, to, &is_obstacle)
+            .map(|hit| hit.last_free_origin)
+            .unwrap_or(to)
+    };
+
+    let origin = first_hit.last_free_origin;
+    let origin = move_until_blocked(origin, Point::new(to.x, origin.y));
+    move_until_blocked(origin, Point::new(origin.x, to.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_object_does_not_tunnel_through_thin_wall() {
+        // A 1x3 projectile moving 20 pixels in one tick, past a wall one pixel thick
+        let collision_points =
+            PixelMask::from_points([Point::new(0, 0), Point::new(0, 1), Point::new(0, 2)]);
+        let is_wall = |point: Point| point.y == -10;
+
+        assert!(!is_wall(Point::new(0, 0)) && !is_wall(Point::new(0, -20)));
+        let hit = sweep_collision(
+            &collision_points,
+            Point::new(0, 0),
+            Point::new(0, -20),
+            is_wall,
+        )
+        .unwrap();
+
+        assert_eq!(hit.origin, Point::new(0, -10));
+        assert_eq!(hit.last_free_origin, Point::new(0, -9));
+        assert_eq!(hit.contact_point, Point::new(0, -10));
+    }
+
+    #[test]
+    fn no_hit_when_path_is_clear() {
+        let collision_points = PixelMask::from_points([Point::new(0, 0)]);
+
+        assert_eq!(
+            sweep_collision(
+                &collision_points,
+                Point::new(0, 0),
+                Point::new(5, 5),
+                |point| point.x > 5
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn sliding_stops_flush_against_wall() {
+        // Moving diagonally into a floor that starts at y = 595
+        let collision_points = PixelMask::from_points([Point::new(0, 0)]);
+        let resolved = resolve_sliding_movement(
+            &collision_points,
+            Point::new(100, 590),
+            Point::new(110, 600),
+            |point| point.y >= 595,
+        );
+
+        assert_eq!(resolved, Point::new(110, 594));
+    }
+}